@@ -2,6 +2,7 @@ use criterion::{criterion_group, criterion_main};
 
 // Import benchmark modules
 mod tiny_lru_bench;
+mod clock_bench;
 mod lru_rs_bench;
 mod const_lru_bench;
 mod schnellru_bench;
@@ -9,7 +10,12 @@ mod uluru_bench;
 
 // Re-export the benchmark functions
 use tiny_lru_bench::{
-    benchmark_push, benchmark_get, benchmark_peek, benchmark_pop
+    benchmark_push, benchmark_get, benchmark_peek, benchmark_pop, benchmark_get_str,
+    benchmark_get_large, benchmark_push_large, benchmark_evict_spilled, benchmark_skewed
+};
+use clock_bench::{
+    benchmark_clock_push, benchmark_clock_get, benchmark_clock_peek, benchmark_clock_pop,
+    benchmark_clock_evict_spilled
 };
 use lru_rs_bench::{
     benchmark_lru_rs_put, benchmark_lru_rs_get, benchmark_lru_rs_peek, benchmark_lru_rs_pop_lru
@@ -30,7 +36,21 @@ criterion_group!(
     benchmark_push,
     benchmark_get,
     benchmark_peek,
-    benchmark_pop
+    benchmark_pop,
+    benchmark_get_str,
+    benchmark_get_large,
+    benchmark_push_large,
+    benchmark_evict_spilled,
+    benchmark_skewed
+);
+
+criterion_group!(
+    clock_benches,
+    benchmark_clock_push,
+    benchmark_clock_get,
+    benchmark_clock_peek,
+    benchmark_clock_pop,
+    benchmark_clock_evict_spilled
 );
 
 criterion_group!(
@@ -72,6 +92,11 @@ criterion_group!(
     benchmark_get,
     benchmark_peek,
     benchmark_pop,
+    // CLOCK benchmarks
+    benchmark_clock_push,
+    benchmark_clock_get,
+    benchmark_clock_peek,
+    benchmark_clock_pop,
     // lru-rs benchmarks
     benchmark_lru_rs_put,
     benchmark_lru_rs_get,
@@ -0,0 +1,146 @@
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
+use std::hint::black_box;
+use tiny_lru::ClockCache;
+
+// Helper function to create a CLOCK cache with N elements
+fn setup_clock_cache_with_n_elements<const N: usize>(n: usize) -> ClockCache<u32, u32, N> {
+    let mut cache = ClockCache::new();
+    for i in 0..n {
+        cache.push(i as u32, (i * 2) as u32);
+    }
+    cache
+}
+
+// Helper function to get powers of 2 from 2 to 128
+fn powers_of_2() -> Vec<usize> {
+    (1..=7).map(|i| 2usize.pow(i)).collect() // 2, 4, 8, 16, 32, 64, 128
+}
+
+pub fn benchmark_clock_push(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clock_push");
+    
+    for n in powers_of_2() {
+        group.bench_with_input(BenchmarkId::new("push", n), &n, |b, &n| {
+            b.iter_batched_ref(
+                ClockCache::<u32, u32, 128>::new,
+                |cache| {
+                    for i in 0..n {
+                        cache.push(black_box(i as u32), black_box((i * 2) as u32));
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    
+    group.finish();
+}
+
+pub fn benchmark_clock_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clock_get");
+    
+    for n in powers_of_2() {
+        let cache = setup_clock_cache_with_n_elements::<128>(n);
+        
+        group.bench_with_input(BenchmarkId::new("get", n), &n, |b, &n| {
+            b.iter_batched_ref(
+                || cache.clone(),
+                |cache| {
+                    for i in 0..n {
+                        black_box(cache.get(&(i as u32)));
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    
+    group.finish();
+}
+
+pub fn benchmark_clock_peek(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clock_peek");
+    
+    for n in powers_of_2() {
+        let cache = setup_clock_cache_with_n_elements::<128>(n);
+        
+        group.bench_with_input(BenchmarkId::new("peek", n), &n, |b, &n| {
+            b.iter_batched_ref(
+                || cache.clone(),
+                |cache| {
+                    for i in 0..n {
+                        black_box(cache.peek(&(i as u32)));
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    
+    group.finish();
+}
+
+pub fn benchmark_clock_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clock_pop");
+    
+    for n in powers_of_2() {
+        group.bench_with_input(BenchmarkId::new("pop", n), &n, |b, &n| {
+            b.iter_batched_ref(
+                || setup_clock_cache_with_n_elements::<128>(n),
+                |cache| {
+                    for _ in 0..n {
+                        black_box(cache.pop());
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    
+    group.finish();
+}
+
+// Same steady-state churn as `evict_spilled` in tiny_lru_bench: every push into the full
+// cache reuses the victim's slot, or a middle entry is removed first (`remove_push`).
+pub fn benchmark_clock_evict_spilled(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clock_evict_spilled");
+
+    for cap in [64usize, 1024, 16384] {
+        group.bench_with_input(BenchmarkId::new("push", cap), &cap, |b, &cap| {
+            let mut cache = ClockCache::<u32, u32, 8>::with_capacity(cap);
+            for i in 0..cap as u32 {
+                cache.push(i, i);
+            }
+            let mut next = cap as u32;
+            b.iter(|| {
+                cache.push(black_box(next), next);
+                next = next.wrapping_add(1);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("remove_push", cap), &cap, |b, &cap| {
+            let mut cache = ClockCache::<u32, u32, 8>::with_capacity(cap);
+            for i in 0..cap as u32 {
+                cache.push(i, i);
+            }
+            let mut next = cap as u32;
+            b.iter(|| {
+                black_box(cache.remove(&(next - cap as u32 / 2)));
+                cache.push(black_box(next), next);
+                next += 1;
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    clock_benches,
+    benchmark_clock_push,
+    benchmark_clock_get,
+    benchmark_clock_peek,
+    benchmark_clock_pop,
+    benchmark_clock_evict_spilled
+);
+criterion_main!(clock_benches);
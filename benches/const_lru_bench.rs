@@ -1,4 +1,5 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
+use std::hint::black_box;
 use const_lru::ConstLru;
 
 // Helper function to create const-lru cache with N elements
@@ -21,7 +22,7 @@ pub fn benchmark_const_lru_insert(c: &mut Criterion) {
     for n in powers_of_2() {
         group.bench_with_input(BenchmarkId::new("insert", n), &n, |b, &n| {
             b.iter_batched_ref(
-                || ConstLru::<u32, u32, 128>::new(),
+                ConstLru::<u32, u32, 128>::new,
                 |cache| {
                     for i in 0..n {
                         cache.insert(black_box(i as u32), black_box((i * 2) as u32));
@@ -89,7 +90,7 @@ pub fn benchmark_const_lru_remove(c: &mut Criterion) {
                 |cache| {
                     for _ in 0..n {
                         // Replicate pop_lru() by getting LRU key first, then removing it
-                        if let Some((lru_key, _)) = cache.iter().last() {
+                        if let Some((lru_key, _)) = cache.iter().next_back() {
                             let key_to_remove = *lru_key; // Clone the key to avoid borrow conflict
                             black_box(cache.remove(&key_to_remove));
                         }
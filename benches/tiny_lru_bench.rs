@@ -1,5 +1,7 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use tiny_lru::TinyLru;
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion, BenchmarkId};
+use std::hint::black_box;
+use tiny_lru::{SieveCache, TinyLru};
 
 // Helper function to create cache with N elements
 fn setup_cache_with_n_elements<const N: usize>(n: usize) -> TinyLru<u32, u32, N> {
@@ -21,7 +23,7 @@ pub fn benchmark_push(c: &mut Criterion) {
     for n in powers_of_2() {
         group.bench_with_input(BenchmarkId::new("push", n), &n, |b, &n| {
             b.iter_batched_ref(
-                || TinyLru::<u32, u32, 128>::new(),
+                TinyLru::<u32, u32, 128>::new,
                 |cache| {
                     for i in 0..n {
                         cache.push(black_box(i as u32), black_box((i * 2) as u32));
@@ -99,5 +101,187 @@ pub fn benchmark_pop(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(tiny_lru_benches, benchmark_push, benchmark_get, benchmark_peek, benchmark_pop);
+// String keys sharing a long prefix, where each full key comparison is expensive.
+// Run with and without `--features fingerprint` to compare the tag scan.
+pub fn benchmark_get_str(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_str");
+
+    // A full cache of each inline capacity, so every lookup scans N slots
+    get_str_inline::<16>(&mut group);
+    get_str_inline::<32>(&mut group);
+    get_str_inline::<64>(&mut group);
+
+    group.finish();
+}
+
+fn get_str_inline<const N: usize>(group: &mut BenchmarkGroup<'_, WallTime>) {
+    let keys: Vec<String> = (0..N).map(|i| format!("session:user:{:08}", i)).collect();
+    let mut cache = TinyLru::<String, u32, N>::new();
+    for (i, key) in keys.iter().enumerate() {
+        cache.push(key.clone(), i as u32);
+    }
+    assert!(!cache.is_spilled());
+
+    group.bench_with_input(BenchmarkId::new("get_str", N), &N, |b, _| {
+        b.iter_batched_ref(
+            || cache.clone(),
+            |cache| {
+                for key in &keys {
+                    black_box(cache.get(black_box(key.as_str())));
+                }
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+// 256-byte values: with the default layout every scanned slot drags its value through
+// the cache. Run with and without `--features soa` to compare layouts.
+type LargeValue = [u64; 32];
+
+pub fn benchmark_get_large(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_large");
+
+    for n in [8, 16, 32, 64] {
+        let mut cache = TinyLru::<u32, LargeValue, 64>::new();
+        for i in 0..n {
+            cache.push(i as u32, [i as u64; 32]);
+        }
+
+        group.bench_with_input(BenchmarkId::new("get_large", n), &n, |b, &n| {
+            b.iter_batched_ref(
+                || cache.clone(),
+                |cache| {
+                    for i in 0..n {
+                        black_box(cache.get(&(i as u32)).map(|v| v[0]));
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+pub fn benchmark_push_large(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push_large");
+
+    for n in [8, 16, 32, 64] {
+        group.bench_with_input(BenchmarkId::new("push_large", n), &n, |b, &n| {
+            b.iter_batched_ref(
+                TinyLru::<u32, LargeValue, 64>::new,
+                |cache| {
+                    // Two passes: inserts, then updates of existing keys
+                    for round in 0..2u64 {
+                        for i in 0..n {
+                            cache.push(black_box(i as u32), black_box([round; 32]));
+                        }
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+// Steady-state post-spill churn: every push evicts the LRU (`push`), or a middle entry is
+// removed before each push (`remove_push`). Both move the last node into the freed slot
+// and repoint its index record.
+pub fn benchmark_evict_spilled(c: &mut Criterion) {
+    let mut group = c.benchmark_group("evict_spilled");
+
+    for cap in [64usize, 1024, 16384] {
+        group.bench_with_input(BenchmarkId::new("push", cap), &cap, |b, &cap| {
+            let mut cache = TinyLru::<u32, u32, 8>::with_capacity(cap);
+            for i in 0..cap as u32 {
+                cache.push(i, i);
+            }
+            let mut next = cap as u32;
+            b.iter(|| {
+                cache.push(black_box(next), next);
+                next = next.wrapping_add(1);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("remove_push", cap), &cap, |b, &cap| {
+            let mut cache = TinyLru::<u32, u32, 8>::with_capacity(cap);
+            for i in 0..cap as u32 {
+                cache.push(i, i);
+            }
+            let mut next = cap as u32;
+            b.iter(|| {
+                black_box(cache.remove(&(next - cap as u32 / 2)));
+                cache.push(black_box(next), next);
+                next += 1;
+            })
+        });
+    }
+
+    group.finish();
+}
+
+// Skewed key trace: a uniform draw cubed, so low keys dominate. Fixed seed keeps the
+// trace identical across runs and policies.
+fn skewed_keys(len: usize, key_space: u32) -> Vec<u32> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let u = (state >> 11) as f64 / (1u64 << 53) as f64;
+            (u * u * u * key_space as f64) as u32
+        })
+        .collect()
+}
+
+// Read-through over a skewed trace: `get`, and `push` on a miss. SIEVE hits only set a
+// bit while LRU hits relink, so the gap grows with the hit ratio.
+pub fn benchmark_skewed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("skewed");
+
+    for cap in [16usize, 1024] {
+        let keys = skewed_keys(4096, cap as u32 * 8);
+
+        group.bench_with_input(BenchmarkId::new("tiny_lru", cap), &cap, |b, &cap| {
+            let mut cache = TinyLru::<u32, u32, 16>::with_capacity(cap);
+            b.iter(|| {
+                for &key in &keys {
+                    if cache.get(&key).is_none() {
+                        cache.push(black_box(key), key);
+                    }
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("sieve", cap), &cap, |b, &cap| {
+            let mut cache = SieveCache::<u32, u32, 16>::with_capacity(cap);
+            b.iter(|| {
+                for &key in &keys {
+                    if cache.get(&key).is_none() {
+                        cache.push(black_box(key), key);
+                    }
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    tiny_lru_benches,
+    benchmark_push,
+    benchmark_get,
+    benchmark_peek,
+    benchmark_pop,
+    benchmark_get_str,
+    benchmark_get_large,
+    benchmark_push_large,
+    benchmark_evict_spilled,
+    benchmark_skewed
+);
 criterion_main!(tiny_lru_benches);
@@ -1,4 +1,5 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
+use std::hint::black_box;
 use uluru::LRUCache;
 
 // Helper function to create uluru cache with N elements
@@ -21,7 +22,7 @@ pub fn benchmark_uluru_insert(c: &mut Criterion) {
     for n in powers_of_2() {
         group.bench_with_input(BenchmarkId::new("insert", n), &n, |b, &n| {
             b.iter_batched_ref(
-                || LRUCache::<u32, 128>::new(),
+                LRUCache::<u32, 128>::new,
                 |cache| {
                     for i in 0..n {
                         cache.insert(black_box((i * 2) as u32));
@@ -0,0 +1,215 @@
+//! TinyLFU admission filter for [`TinyLru::set_admission`](crate::TinyLru::set_admission).
+//!
+//! A count-min sketch of 4-bit-range counters estimates how often each key has been
+//! seen recently. A doorkeeper bloom filter absorbs the first sighting of every key, so
+//! one-hit wonders never reach the sketch. Every `sample_size` recorded accesses the
+//! counters are halved and the doorkeeper is cleared, so old popularity fades.
+//!
+//! When a push needs to evict, the new key is only admitted if its estimate beats the
+//! LRU victim's; otherwise the victim stays and the new entry is turned away. There is
+//! no window segment in front of the LRU list, so this is plain TinyLFU admission, not
+//! W-TinyLFU: a burst of brand-new keys is turned away until the keys repeat.
+//!
+//! See Einziger, Friedman and Manes, "TinyLFU: A Highly Efficient Cache Admission
+//! Policy" (ACM ToS, 2017).
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Counters saturate here, as 4-bit counters would.
+const MAX_COUNT: u8 = 15;
+
+/// Per-row seeds that turn one key hash into four independent column indices.
+const SEEDS: [u64; 4] = [0xc3a5_c85c_97cb_3127, 0xb492_b66f_be98_f273, 0x9ae1_6a3b_2f90_404f, 0xcbf2_9ce4_8422_2325];
+
+/// Frequency-based admission filter: count-min sketch, doorkeeper and decision counts.
+///
+/// Sized for the cache it guards; a larger `capacity` means fewer collisions. Attach it
+/// with [`TinyLru::set_admission`](crate::TinyLru::set_admission).
+///
+/// # Example
+/// ```
+/// use tiny_lru::{TinyLfu, TinyLru};
+///
+/// let mut cache = TinyLru::<u32, u32, 4>::new();
+/// cache.set_admission(Some(TinyLfu::new(4)));
+/// for key in 0..4 {
+///     cache.push(key, key);
+///     cache.get(&key); // Seen twice: past the doorkeeper
+/// }
+///
+/// cache.push(100, 100); // Seen once, colder than the LRU victim
+/// assert!(!cache.contains_key(&100));
+/// assert!(cache.contains_key(&0));
+///
+/// let filter = cache.admission().unwrap();
+/// assert_eq!((filter.admitted(), filter.rejected()), (0, 1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TinyLfu {
+    // Four rows of `width` counters, row-major
+    table: Vec<u8>,
+    width_mask: usize,
+
+    // Bloom filter of keys seen once since the last reset
+    doorkeeper: Vec<u64>,
+    doorkeeper_mask: usize,
+
+    additions: usize,
+    sample_size: usize,
+
+    admitted: u64,
+    rejected: u64,
+}
+
+impl TinyLfu {
+    /// Create a filter for a cache of `capacity` entries, aging every `10 * capacity`
+    /// recorded accesses.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self::with_sample_size(capacity, capacity.max(1) * 10)
+    }
+
+    /// Create a filter for a cache of `capacity` entries, halving all counts every
+    /// `sample_size` recorded accesses.
+    ///
+    /// # Panics
+    /// Panics if `sample_size == 0`.
+    pub fn with_sample_size(capacity: usize, sample_size: usize) -> Self {
+        assert!(sample_size > 0, "sample_size must be > 0");
+        // Four counters per entry in each row keeps collisions with hot keys rare
+        let width = (capacity.max(4) * 4).next_power_of_two();
+        // Eight bits per access in a sample: a few percent false positives at worst
+        let doorkeeper_bits = (sample_size * 8).max(64).next_power_of_two();
+        Self {
+            table: vec![0; width * SEEDS.len()],
+            width_mask: width - 1,
+            doorkeeper: vec![0; doorkeeper_bits / 64],
+            doorkeeper_mask: doorkeeper_bits - 1,
+            additions: 0,
+            sample_size,
+            admitted: 0,
+            rejected: 0,
+        }
+    }
+
+    /// Number of new keys admitted in place of a victim.
+    #[inline]
+    pub fn admitted(&self) -> u64 {
+        self.admitted
+    }
+
+    /// Number of new keys turned away because the victim was estimated hotter.
+    #[inline]
+    pub fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Accesses recorded between two agings.
+    #[inline]
+    pub fn sample_size(&self) -> usize {
+        self.sample_size
+    }
+
+    /// Forget all frequencies and zero the decision counts.
+    pub fn clear(&mut self) {
+        self.table.fill(0);
+        self.doorkeeper.fill(0);
+        self.additions = 0;
+        self.admitted = 0;
+        self.rejected = 0;
+    }
+
+    /// Record one access to the key with this hash.
+    ///
+    /// The first sighting since the last aging only sets the doorkeeper bits.
+    #[inline]
+    pub(crate) fn record(&mut self, hash: u64) {
+        if self.doorkeeper_insert(hash) {
+            for (row, seed) in SEEDS.iter().enumerate() {
+                let slot = self.slot(row, hash, *seed);
+                let count = &mut self.table[slot];
+                *count = (*count + 1).min(MAX_COUNT);
+            }
+        }
+
+        self.additions += 1;
+        if self.additions >= self.sample_size {
+            self.age();
+        }
+    }
+
+    /// Estimated recent access count of the key with this hash.
+    #[inline]
+    pub(crate) fn estimate(&self, hash: u64) -> u8 {
+        let sketch = SEEDS
+            .iter()
+            .enumerate()
+            .map(|(row, seed)| self.table[self.slot(row, hash, *seed)])
+            .min()
+            .unwrap_or(0);
+        sketch + self.doorkeeper_contains(hash) as u8
+    }
+
+    /// Decide whether a candidate may replace the victim, counting the decision.
+    #[inline]
+    pub(crate) fn admit(&mut self, candidate: u64, victim: u64) -> bool {
+        let admit = self.estimate(candidate) > self.estimate(victim);
+        if admit {
+            self.admitted += 1;
+        } else {
+            self.rejected += 1;
+        }
+        admit
+    }
+
+    /// Halve every counter and clear the doorkeeper.
+    #[cold]
+    fn age(&mut self) {
+        for count in &mut self.table {
+            *count >>= 1;
+        }
+        self.doorkeeper.fill(0);
+        self.additions /= 2;
+    }
+
+    #[inline(always)]
+    fn slot(&self, row: usize, hash: u64, seed: u64) -> usize {
+        row * (self.width_mask + 1) + (mix(hash ^ seed) as usize & self.width_mask)
+    }
+
+    /// The two doorkeeper bit positions for a hash.
+    #[inline(always)]
+    fn doorkeeper_bits(&self, hash: u64) -> [usize; 2] {
+        let mixed = mix(hash);
+        [(mixed as usize) & self.doorkeeper_mask, ((mixed >> 32) as usize) & self.doorkeeper_mask]
+    }
+
+    #[inline(always)]
+    fn doorkeeper_contains(&self, hash: u64) -> bool {
+        self.doorkeeper_bits(hash).iter().all(|&bit| self.doorkeeper[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Set the doorkeeper bits for a hash; returns `true` if they were all set already.
+    #[inline(always)]
+    fn doorkeeper_insert(&mut self, hash: u64) -> bool {
+        let mut seen = true;
+        for bit in self.doorkeeper_bits(hash) {
+            let word = &mut self.doorkeeper[bit / 64];
+            seen &= *word & (1 << (bit % 64)) != 0;
+            *word |= 1 << (bit % 64);
+        }
+        seen
+    }
+}
+
+/// Spread every input bit over the whole word (the MurmurHash3 finalizer), so weak
+/// hashers such as Fx on small integers still give independent indices.
+#[inline(always)]
+fn mix(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^ (x >> 33)
+}
@@ -0,0 +1,439 @@
+//! Adaptive Replacement Cache on top of [`TinyLru`](crate::TinyLru)'s storage and links.
+//!
+//! Resident entries share one intrusive list, split by a boundary pointer as in
+//! [`SlruCache`](crate::SlruCache):
+//!
+//! ```text
+//! head                        boundary                    tail
+//!  [ T1 LRU ... T1 MRU ][ T2 LRU ... T2 MRU ]
+//! ```
+//!
+//! T1 holds keys seen once recently, T2 keys seen at least twice. Evicted keys are
+//! remembered without their values in two ghost lists, B1 (from T1) and B2 (from T2),
+//! each a key-only `TinyLru`. A push that hits a ghost means the cache recently threw away
+//! something it needed: a B1 hit grows the target size `p` of T1, a B2 hit shrinks it,
+//! and eviction takes from T1 while it is over `p`.
+//!
+//! See Megiddo and Modha, "ARC: A Self-Tuning, Low Overhead Replacement Cache"
+//! (FAST '03).
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+use crate::{FxBuildHasher, LinkIndex, TinyLru};
+
+/// A value and the resident list its node is in.
+#[derive(Clone)]
+pub(crate) struct Tiered<V> {
+    value: V,
+    pub(crate) frequent: bool,
+}
+
+/// Adaptive Replacement Cache: balances recency and frequency from its own misses.
+///
+/// Same inline-then-spill storage as [`TinyLru`]: the resident entries and both ghost
+/// lists start inline in `N` slots each, and spill to the heap with a hash index past
+/// that. `capacity` bounds the resident entries, and the ghost lists remember up to as
+/// many evicted keys again.
+///
+/// Ghost hits are detected by [`push`](Self::push), since only a push brings the value
+/// back; a [`get`](Self::get) for an evicted key is a plain miss.
+///
+/// # Example
+/// ```
+/// use tiny_lru::ArcCache;
+///
+/// let mut cache = ArcCache::<u32, &str, 4>::new();
+/// cache.push(1, "a");
+/// cache.get(&1); // Second touch: frequent (T2)
+/// cache.push(2, "b");
+/// assert_eq!((cache.recent_len(), cache.frequent_len()), (1, 1));
+///
+/// for key in 100..104 {
+///     cache.push(key, "scan"); // One-off keys go through T1 and into ghost list B1
+/// }
+/// assert_eq!(cache.get(&1), Some(&"a"));
+///
+/// cache.push(100, "again"); // Ghost hit in B1: give T1 more room
+/// assert_eq!(cache.recent_target(), 1);
+/// ```
+#[derive(Clone)]
+pub struct ArcCache<K, V, const N: usize, S = FxBuildHasher, I = u16> {
+    pub(crate) cache: TinyLru<K, Tiered<V>, N, S, I>,
+
+    // First T2 node; everything before it is in T1. `I::NONE` if T2 is empty.
+    pub(crate) boundary: I,
+    frequent_len: usize,
+
+    // Keys evicted from T1 and T2, most recent at the tail
+    pub(crate) ghost_recent: TinyLru<K, (), N, S, I>,
+    pub(crate) ghost_frequent: TinyLru<K, (), N, S, I>,
+
+    // Target size of T1, adapted on ghost hits; `0..=capacity`
+    p: usize,
+}
+
+impl<K, V, const N: usize, I> ArcCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    /// Create a cache with capacity = N.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(N)
+    }
+
+    /// Create a cache with the given capacity (must be >= N).
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::with_capacity_and_hasher(cap, FxBuildHasher)
+    }
+}
+
+impl<K, V, const N: usize, S, I> ArcCache<K, V, N, S, I>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Clone,
+    I: LinkIndex,
+{
+    /// Create a cache with the given capacity (must be >= N), using `hash_builder` for
+    /// the post-spill indexes of the resident and ghost lists.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity_and_hasher(cap: usize, hash_builder: S) -> Self {
+        Self {
+            cache: TinyLru::with_capacity_and_hasher(cap, hash_builder.clone()),
+            boundary: I::NONE,
+            frequent_len: 0,
+            ghost_recent: TinyLru::with_capacity_and_hasher(cap, hash_builder.clone()),
+            ghost_frequent: TinyLru::with_capacity_and_hasher(cap, hash_builder),
+            p: 0,
+        }
+    }
+
+    /// Insert or update a key-value pair.
+    ///
+    /// Updating counts as a hit and moves the entry to T2. A key found in a ghost list
+    /// adapts the T1 target and comes back straight into T2; any other new key enters T1.
+    /// Makes room first if the cache is full.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        if let Some(index) = self.cache.find_key_index(&key) {
+            self.cache.store.val_mut(index).value = value;
+            self.cache.counters.update();
+            self.touch(index);
+            return;
+        }
+
+        let cap = self.cache.capacity();
+        let (b1, b2) = (self.ghost_recent.len(), self.ghost_frequent.len());
+        if b1 > 0 && self.ghost_recent.discard(&key) {
+            self.p = cap.min(self.p + (b2 / b1).max(1));
+            self.replace(false);
+            self.insert_frequent(key, value);
+            return;
+        }
+        if b2 > 0 && self.ghost_frequent.discard(&key) {
+            self.p = self.p.saturating_sub((b1 / b2).max(1));
+            self.replace(true);
+            self.insert_frequent(key, value);
+            return;
+        }
+
+        // Brand new key: keep T1 + B1 and the whole directory within bounds
+        let recent = self.recent_len();
+        if recent + b1 >= cap {
+            if recent < cap {
+                self.ghost_recent.pop();
+                self.replace(false);
+            } else {
+                // T1 alone fills the cache: drop its LRU without a ghost
+                self.unlink(self.cache.head.to_usize());
+                self.cache.counters.evict();
+            }
+        } else {
+            if self.cache.len() + b1 + b2 >= 2 * cap {
+                self.ghost_frequent.pop();
+            }
+            self.replace(false);
+        }
+
+        // Room is guaranteed now, so this only spills
+        self.cache.make_room();
+        self.cache.insert(key, Tiered { value, frequent: false });
+        if self.boundary != I::NONE {
+            self.cache.move_before(self.cache.tail.to_usize(), self.boundary.to_usize());
+        }
+    }
+
+    /// Get a value by key. A hit moves the entry to the MRU end of T2.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_hit(key)?;
+        Some(&self.cache.store.val(index).value)
+    }
+
+    /// Get a mutable reference by key, with the same promotion as [`get`](Self::get).
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_hit(key)?;
+        Some(&mut self.cache.store.val_mut(index).value)
+    }
+
+    /// Peek at a value by key without promoting it.
+    #[inline]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.peek(key).map(|t| &t.value)
+    }
+
+    /// Returns `true` if the key is resident. Ghost keys do not count.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.contains_key(key)
+    }
+
+    /// Remove and return the entry a full-cache push would evict next. The key is
+    /// handed back rather than kept as a ghost.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.cache.is_empty() {
+            return None;
+        }
+        let index = self.victim(false);
+        let (key, t) = self.unlink(index);
+        Some((key, t.value))
+    }
+
+    /// Remove a resident key-value pair and return it.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.cache.find_key_index(key)?;
+        let (key, t) = self.unlink(index);
+        Some((key, t.value))
+    }
+
+    /// Remove all entries and ghost keys, and reset the T1 target.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.ghost_recent.clear();
+        self.ghost_frequent.clear();
+        self.boundary = I::NONE;
+        self.frequent_len = 0;
+        self.p = 0;
+    }
+
+    /// Adjust the cache capacity, with the same rules as
+    /// [`TinyLru::set_capacity`](crate::TinyLru::set_capacity).
+    ///
+    /// Shrinking below the old capacity forgets the oldest ghost keys and lowers the T1
+    /// target to fit; resident entries are never evicted.
+    ///
+    /// # Panics
+    /// Panics if `new_cap <= len`, `new_cap < N` or `new_cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn set_capacity(&mut self, new_cap: usize) {
+        self.cache.set_capacity(new_cap);
+        self.p = self.p.min(new_cap);
+
+        self.ghost_recent.resize_evict(new_cap, |_, _| {});
+        self.ghost_frequent.resize_evict(new_cap, |_, _| {});
+
+        // Forget the oldest ghosts until T1 + B1 <= c and the directory <= 2c
+        while self.recent_len() + self.ghost_recent.len() > new_cap {
+            self.ghost_recent.pop();
+        }
+        while self.cache.len() + self.ghost_recent.len() + self.ghost_frequent.len() > 2 * new_cap {
+            self.ghost_frequent.pop();
+        }
+    }
+
+    /// Returns the number of resident entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if no entries are resident.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the maximum number of resident entries.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+
+    /// Returns the number of entries seen once recently (T1).
+    #[inline]
+    pub fn recent_len(&self) -> usize {
+        self.cache.len() - self.frequent_len
+    }
+
+    /// Returns the number of entries seen at least twice (T2).
+    #[inline]
+    pub fn frequent_len(&self) -> usize {
+        self.frequent_len
+    }
+
+    /// Returns the number of ghost keys evicted from T1 (B1) and from T2 (B2).
+    #[inline]
+    pub fn ghost_len(&self) -> (usize, usize) {
+        (self.ghost_recent.len(), self.ghost_frequent.len())
+    }
+
+    /// Returns the current target size of T1, `p`.
+    #[inline]
+    pub fn recent_target(&self) -> usize {
+        self.p
+    }
+
+    /// Returns `true` if the resident entries have spilled to the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        self.cache.is_spilled()
+    }
+
+    /// Returns a snapshot of the operation counters for the resident entries (`stats`
+    /// feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> crate::CacheStats {
+        self.cache.stats()
+    }
+
+    /// Zero all operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.cache.reset_stats();
+    }
+
+    /// Look up a key for a promoting access, counting the hit or miss.
+    #[inline(always)]
+    fn find_hit<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.cache.find_key_index(key);
+        self.cache.counters.lookup(index.is_some());
+        let index = index?;
+        self.touch(index);
+        Some(index)
+    }
+
+    /// Record a hit on the entry at `index`: move it to the MRU end of T2.
+    #[inline(always)]
+    fn touch(&mut self, index: usize) {
+        let frequent = &mut self.cache.store.val_mut(index).frequent;
+        if *frequent {
+            // Leaving the boundary slot: the next T2 node takes over
+            if index == self.boundary.to_usize() && self.cache.store.next(index) != I::NONE {
+                self.boundary = self.cache.store.next(index);
+            }
+        } else {
+            *frequent = true;
+            self.frequent_len += 1;
+            if self.boundary == I::NONE {
+                self.boundary = I::from_usize(index);
+            }
+        }
+        self.cache.promote_to_mru(index);
+    }
+
+    /// Insert a key returning from a ghost list at the MRU end of T2. The cache must
+    /// have room.
+    #[inline(always)]
+    fn insert_frequent(&mut self, key: K, value: V) {
+        self.cache.make_room();
+        self.cache.insert(key, Tiered { value, frequent: true });
+        self.frequent_len += 1;
+        if self.boundary == I::NONE {
+            self.boundary = self.cache.tail;
+        }
+    }
+
+    /// Slot of the next victim: the T1 LRU while T1 is over its target, else the T2 LRU.
+    ///
+    /// `ghost_frequent_hit` breaks the tie at `|T1| == p` in favour of T1, as a B2 hit
+    /// asks T2 to keep its entries.
+    #[inline(always)]
+    fn victim(&self, ghost_frequent_hit: bool) -> usize {
+        let recent = self.recent_len();
+        let from_recent = recent > 0
+            && (self.frequent_len == 0 || recent > self.p || (ghost_frequent_hit && recent == self.p));
+        if from_recent { self.cache.head.to_usize() } else { self.boundary.to_usize() }
+    }
+
+    /// Evict one resident entry into its ghost list if the cache is full.
+    #[inline(always)]
+    fn replace(&mut self, ghost_frequent_hit: bool) {
+        if self.cache.len() < self.cache.capacity() {
+            return;
+        }
+        let (key, t) = self.unlink(self.victim(ghost_frequent_hit));
+        self.cache.counters.evict();
+        if t.frequent {
+            self.ghost_frequent.push(key, ());
+        } else {
+            self.ghost_recent.push(key, ());
+        }
+    }
+
+    /// Remove the entry at `index`, keeping the boundary and T2 size in step.
+    #[inline(always)]
+    fn unlink(&mut self, index: usize) -> (K, Tiered<V>) {
+        let last = self.cache.len() - 1;
+        if index == self.boundary.to_usize() {
+            self.boundary = self.cache.store.next(index);
+        }
+        // `swap_remove` moves the last slot into `index`
+        if self.boundary.to_usize() == last && index != last {
+            self.boundary = I::from_usize(index);
+        }
+
+        let (key, t) = self.cache.unlink_at(index);
+        self.frequent_len -= t.frequent as usize;
+        (key, t)
+    }
+}
+
+impl<K, V, const N: usize, I> Default for ArcCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
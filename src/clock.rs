@@ -0,0 +1,436 @@
+//! CLOCK (second-chance) eviction over the inline-then-spill store, with no list links.
+//!
+//! Slots are kept in a ring in storage order. A hit sets the slot's reference bit. To
+//! make room, the hand walks the ring clearing set bits and stops at the first clear
+//! one; the new entry is written into that slot in place and the hand moves past it.
+//! Removal still compacts with `swap_remove`, and the hand follows the moved slot.
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use core::mem;
+
+use hashbrown::HashTable;
+
+use crate::store::Store;
+use crate::{FxBuildHasher, LinkIndex, assert_capacity_limit, make_hash, slot_index, stats};
+
+/// A ring slot: the entry and its reference bit.
+#[derive(Clone)]
+pub(crate) struct Slot<K, V> {
+    pub(crate) key: K,
+    value: V,
+    pub(crate) referenced: bool,
+}
+
+/// Cache with CLOCK eviction: no linked list, one reference bit per slot.
+///
+/// Same inline-then-spill behaviour as [`TinyLru`](crate::TinyLru): up to `N` entries
+/// live inline and are found by a linear scan; past `N` the store moves to the heap with
+/// a hash index, and stays spilled until [`clear`](Self::clear), [`resize`](Self::resize)
+/// or [`unspill`](Self::unspill). A hit writes one bit instead of relinking two
+/// nodes, and a push into a full cache reuses the victim's slot.
+///
+/// Eviction approximates LRU: an entry survives one pass of the hand for each hit since
+/// the last pass.
+///
+/// # Example
+/// ```
+/// use tiny_lru::ClockCache;
+///
+/// let mut cache = ClockCache::<u32, &str, 3>::new();
+/// cache.push(1, "a");
+/// cache.push(2, "b");
+/// cache.push(3, "c");
+/// cache.get(&1);
+///
+/// cache.push(4, "d"); // Hand gives 1 a second chance, evicts 2
+/// assert!(cache.contains_key(&1));
+/// assert!(!cache.contains_key(&2));
+/// ```
+#[derive(Clone)]
+pub struct ClockCache<K, V, const N: usize, S = FxBuildHasher, I = u16> {
+    // Ring of entries in storage order; compact, like `TinyLru`'s store.
+    pub(crate) slots: Store<Slot<K, V>, N>,
+
+    // Hash → slot table, allocated on the first spill. Pre-spill lookups scan `slots`.
+    pub(crate) index: Option<HashTable<I>>,
+
+    // Slot the hand examines next; below `slots.len()` unless the cache is empty.
+    pub(crate) hand: I,
+
+    capacity: I,
+    hash_builder: S,
+
+    // Operation counters; zero-sized and inert without the `stats` feature.
+    counters: stats::Counters,
+}
+
+impl<K, V, const N: usize, I> ClockCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    /// Create a cache with capacity = N.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(N)
+    }
+
+    /// Create a cache with the given capacity (must be >= N).
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::with_capacity_and_hasher(cap, FxBuildHasher)
+    }
+}
+
+impl<K, V, const N: usize, S, I> ClockCache<K, V, N, S, I>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    /// Create a cache with the given capacity (must be >= N), using `hash_builder` for
+    /// the post-spill index.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity_and_hasher(cap: usize, hash_builder: S) -> Self {
+        assert_capacity_limit::<N, I>();
+        assert!(cap >= N, "capacity must be >= N");
+        assert!(cap <= I::MAX_CAPACITY, "capacity must be <= I::MAX_CAPACITY");
+
+        Self {
+            slots: Store::new(),
+            index: None,
+            hand: I::from_usize(0),
+            capacity: I::from_usize(cap),
+            hash_builder,
+            counters: stats::Counters::new(),
+        }
+    }
+
+    /// Insert or update a key-value pair.
+    ///
+    /// Updating sets the reference bit. A new key starts with the bit clear; if the
+    /// cache is full it takes the slot of the entry the hand evicts.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        self.push_evict(key, value);
+    }
+
+    /// Insert or update a key-value pair, returning the displaced pair.
+    ///
+    /// As with [`TinyLru::push_evict`](crate::TinyLru::push_evict), an update returns the
+    /// passed key together with the old value, and a new key in a full cache returns the
+    /// entry the hand evicted. Otherwise returns `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::ClockCache;
+    ///
+    /// let mut cache = ClockCache::<u32, &str, 2>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    /// assert_eq!(cache.push_evict(3, "c"), Some((1, "a")));
+    /// assert_eq!(cache.push_evict(3, "C"), Some((3, "c"))); // Update
+    /// assert_eq!(cache.push_evict(3, "D"), Some((3, "C")));
+    /// ```
+    #[inline]
+    pub fn push_evict(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(index) = self.find_key_index(&key) {
+            let slot = &mut self.slots[index];
+            let old = mem::replace(&mut slot.value, value);
+            slot.referenced = true;
+            self.counters.update();
+            return Some((key, old));
+        }
+
+        self.counters.insert();
+        let len = self.slots.len();
+        if len >= self.capacity.to_usize() && len > 0 {
+            return Some(self.replace_victim(key, value));
+        }
+
+        if len == N && self.index.is_none() {
+            self.spill();
+        }
+        if let Some(map) = &mut self.index {
+            let (slots, hasher) = (&self.slots, &self.hash_builder);
+            slot_index::insert(map, make_hash(hasher, &key), len, hasher, |i| &slots[i].key);
+        }
+        self.slots.push(Slot { key, value, referenced: false });
+        None
+    }
+
+    /// Get a value by key, setting its reference bit.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Get a mutable reference by key, setting its reference bit.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_key_index(key);
+        self.counters.lookup(index.is_some());
+        let slot = &mut self.slots[index?];
+        slot.referenced = true;
+        Some(&mut slot.value)
+    }
+
+    /// Peek at a value by key without setting its reference bit.
+    #[inline]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_key_index(key);
+        self.counters.peek(index.is_some());
+        Some(&self.slots[index?].value)
+    }
+
+    /// Returns `true` if the key is present. Does not set its reference bit.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_key_index(key).is_some()
+    }
+
+    /// Evict and return the entry the hand selects next, clearing reference bits on the
+    /// way as a full-cache `push` would.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.slots.is_empty() {
+            return None;
+        }
+        let victim = self.sweep();
+        Some(self.remove_at(victim))
+    }
+
+    /// Remove a key-value pair and return it.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_key_index(key)?;
+        Some(self.remove_at(index))
+    }
+
+    /// Remove all entries, keeping the current buffer for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.index = None;
+        self.hand = I::from_usize(0);
+    }
+
+    /// Adjust the cache capacity.
+    ///
+    /// # Panics
+    /// Panics if `new_cap <= len`, `new_cap < N` or `new_cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn set_capacity(&mut self, new_cap: usize) {
+        assert!(new_cap > self.slots.len(), "new_cap must be > current size");
+        assert!(new_cap >= N, "new_cap must be >= N");
+        assert!(new_cap <= I::MAX_CAPACITY, "new_cap must be <= I::MAX_CAPACITY");
+        self.capacity = I::from_usize(new_cap);
+    }
+
+    /// Change the capacity, evicting entries chosen by the hand until the rest fit.
+    ///
+    /// Evicted entries are returned in eviction order. If the remaining entries fit in
+    /// `N`, the cache moves back to inline storage and drops its index.
+    ///
+    /// # Panics
+    /// Panics if `new_cap < N` or `new_cap > I::MAX_CAPACITY`.
+    pub fn resize(&mut self, new_cap: usize) -> Vec<(K, V)> {
+        assert!(new_cap >= N, "new_cap must be >= N");
+        assert!(new_cap <= I::MAX_CAPACITY, "new_cap must be <= I::MAX_CAPACITY");
+
+        let mut evicted = Vec::new();
+        while self.slots.len() > new_cap {
+            let victim = self.sweep();
+            evicted.push(self.remove_at(victim));
+            self.counters.evict();
+        }
+        self.capacity = I::from_usize(new_cap);
+        self.unspill();
+        evicted
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the cache holds no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Returns the maximum number of entries.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity.to_usize()
+    }
+
+    /// Returns `true` if the entries have spilled to the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// Returns `true` if the cache is spilled but its entries fit in `N`.
+    #[inline]
+    pub fn can_unspill(&self) -> bool {
+        self.is_spilled() && self.slots.len() <= N
+    }
+
+    /// Move the entries back to inline storage and drop the index, if they fit.
+    ///
+    /// Returns `false` if the cache is not spilled or holds more than `N` entries.
+    #[inline]
+    pub fn unspill(&mut self) -> bool {
+        if !self.can_unspill() {
+            return false;
+        }
+        self.slots.shrink_to_fit();
+        self.index = None;
+        self.counters.unspill();
+        true
+    }
+
+    /// Returns a snapshot of the operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> crate::CacheStats {
+        self.counters.snapshot()
+    }
+
+    /// Zero all operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.counters = stats::Counters::new();
+    }
+
+    /// Find the slot holding a key: linear scan pre-spill, hash probe post-spill.
+    #[inline(always)]
+    pub(crate) fn find_key_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(index) = &self.index {
+            let slots = &self.slots;
+            slot_index::find(index, &self.hash_builder, key, |i| &slots[i].key)
+        } else {
+            self.slots.iter().position(|slot| slot.key.borrow() == key)
+        }
+    }
+
+    /// Build the index over the current slots.
+    #[cold]
+    fn spill(&mut self) {
+        let slots = &self.slots;
+        self.index = Some(slot_index::build(slots.len(), &self.hash_builder, |i| &slots[i].key));
+        self.counters.spill();
+    }
+
+    /// Advance the hand to the next slot with a clear reference bit, clearing set bits
+    /// on the way, and return it. The cache must not be empty.
+    ///
+    /// Terminates within one lap plus one step: the first lap clears every bit.
+    #[inline(always)]
+    fn sweep(&mut self) -> usize {
+        let len = self.slots.len();
+        let mut hand = self.hand.to_usize();
+        loop {
+            let slot = &mut self.slots[hand];
+            if !slot.referenced {
+                self.hand = I::from_usize(hand);
+                return hand;
+            }
+            slot.referenced = false;
+            hand += 1;
+            if hand == len {
+                hand = 0;
+            }
+        }
+    }
+
+    /// Evict the hand's victim and write the new entry into its slot, in place.
+    #[inline(always)]
+    fn replace_victim(&mut self, key: K, value: V) -> (K, V) {
+        let victim = self.sweep();
+        let old = mem::replace(&mut self.slots[victim], Slot { key, value, referenced: false });
+        if let Some(map) = &mut self.index {
+            // Same slot id, new key: swap the record into the new key's bucket
+            let (slots, hasher) = (&self.slots, &self.hash_builder);
+            slot_index::unindex(map, make_hash(hasher, &old.key), victim);
+            slot_index::insert(map, make_hash(hasher, &slots[victim].key), victim, hasher, |i| &slots[i].key);
+        }
+
+        let next = victim + 1;
+        self.hand = I::from_usize(if next == self.slots.len() { 0 } else { next });
+        self.counters.evict();
+        (old.key, old.value)
+    }
+
+    /// Remove the entry at `index`, moving the last slot into its place.
+    ///
+    /// The hand keeps pointing at the same entry if that entry moved, and otherwise at
+    /// whatever now occupies its slot.
+    #[inline(always)]
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        let last = self.slots.len() - 1;
+        if let Some(map) = &mut self.index {
+            slot_index::unindex(map, make_hash(&self.hash_builder, &self.slots[index].key), index);
+        }
+
+        let slot = self.slots.swap_remove(index);
+        if index < last
+            && let Some(map) = &mut self.index
+        {
+            slot_index::reindex(map, make_hash(&self.hash_builder, &self.slots[index].key), last, index);
+        }
+
+        let hand = self.hand.to_usize();
+        if hand == last {
+            self.hand = I::from_usize(if index < last { index } else { 0 });
+        }
+        (slot.key, slot.value)
+    }
+}
+
+impl<K, V, const N: usize, I> Default for ClockCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
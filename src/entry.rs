@@ -0,0 +1,292 @@
+//! Entry API for single-lookup upserts on a [`TinyLru`](crate::TinyLru).
+//!
+//! Modelled on `std::collections::hash_map::Entry`. The lookup done by
+//! [`TinyLru::entry`](crate::TinyLru::entry) is carried in the entry, so the
+//! pre-spill linear scan (or post-spill hash probe) happens exactly once.
+
+use core::hash::{BuildHasher, Hash};
+
+use likely_stable::unlikely;
+
+use crate::{EvictionListener, EvictionReason, LinkIndex, TinyLru};
+
+/// A view into a single cache slot, which may be occupied or vacant.
+///
+/// Created by [`TinyLru::entry`](crate::TinyLru::entry).
+pub enum Entry<'a, K, V, const N: usize, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    /// The key is present; it has already been promoted to MRU.
+    Occupied(OccupiedEntry<'a, K, V, N, S, I, L>),
+    /// The key is absent.
+    Vacant(VacantEntry<'a, K, V, N, S, I, L>),
+}
+
+/// A view into an occupied slot. Part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K, V, const N: usize, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    cache: &'a mut TinyLru<K, V, N, S, I, L>,
+    index: usize,
+}
+
+/// A view into a vacant slot. Part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K, V, const N: usize, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    cache: &'a mut TinyLru<K, V, N, S, I, L>,
+    key: K,
+}
+
+impl<'a, K, V, const N: usize, S, I, L> Entry<'a, K, V, N, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    /// Returns a reference to this entry's key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Ensure a value is present by inserting `default` if vacant.
+    ///
+    /// Returns a mutable reference to the value.
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensure a value is present by inserting the result of `default` if vacant.
+    ///
+    /// `default` is only called on a miss. Returns a mutable reference to the value.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, String, 2>::new();
+    /// let val = cache.entry(1).or_insert_with(|| "computed".to_string());
+    /// assert_eq!(val, "computed");
+    /// ```
+    #[inline]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Like [`or_insert_with`](Self::or_insert_with), but `default` receives the key.
+    #[inline]
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Ensure a value is present by inserting `V::default()` if vacant.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Apply `f` to the value if the entry is occupied, then return the entry.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<&str, u32, 2>::new();
+    /// cache.entry("a").and_modify(|v| *v += 1).or_insert(1);
+    /// cache.entry("a").and_modify(|v| *v += 1).or_insert(1);
+    /// assert_eq!(cache.peek(&"a"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            vacant => vacant,
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize, S, I, L> OccupiedEntry<'a, K, V, N, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    #[inline]
+    pub(crate) fn new(cache: &'a mut TinyLru<K, V, N, S, I, L>, index: usize) -> Self {
+        Self { cache, index }
+    }
+
+    /// Returns a reference to the key in the cache.
+    #[inline]
+    pub fn key(&self) -> &K {
+        self.cache.store.key(self.index)
+    }
+
+    /// Returns a reference to the value.
+    #[inline]
+    pub fn get(&self) -> &V {
+        self.cache.store.val(self.index)
+    }
+
+    /// Returns a mutable reference to the value.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        self.cache.store.val_mut(self.index)
+    }
+
+    /// Converts the entry into a mutable reference to the value, bound to the cache borrow.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        self.cache.store.val_mut(self.index)
+    }
+
+    /// Replace the value, returning the old one.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the entry from the cache, returning the value.
+    #[inline]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Remove the entry from the cache, returning the owned key and value.
+    #[inline]
+    pub fn remove_entry(self) -> (K, V) {
+        self.cache.remove_at(self.index)
+    }
+}
+
+impl<'a, K, V, const N: usize, S, I, L> VacantEntry<'a, K, V, N, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    #[inline]
+    pub(crate) fn new(cache: &'a mut TinyLru<K, V, N, S, I, L>, key: K) -> Self {
+        Self { cache, key }
+    }
+
+    /// Returns a reference to the key that would be inserted.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take ownership of the key without inserting.
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insert the value as the MRU entry, evicting the LRU entry if at capacity.
+    ///
+    /// The evicted entry goes to the cache's [`EvictionListener`]. If the cache is full
+    /// and every entry is [pinned](crate::TinyLru::pin), the LRU entry is evicted anyway
+    /// and loses its pin; use [`try_insert`](Self::try_insert) to refuse instead.
+    /// Returns a mutable reference to the inserted value.
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        let cache = self.cache;
+        if unlikely(cache.pinned_full()) {
+            let (old_key, old_value) = cache.evict_pinned_lru();
+            cache.listener.on_evict(old_key, old_value, EvictionReason::Capacity);
+        }
+        cache.insert_notify(self.key, value);
+        // The new entry is always appended and linked as the tail.
+        let tail = cache.tail.to_usize();
+        cache.store.val_mut(tail)
+    }
+
+    /// Like [`insert`](Self::insert), but hands the key and value back, leaving the cache
+    /// unchanged, if it is full and every entry is [pinned](crate::TinyLru::pin).
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::{Entry, TinyLru};
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 1>::new();
+    /// cache.push(1, "a");
+    /// cache.pin(&1);
+    ///
+    /// if let Entry::Vacant(entry) = cache.entry(2) {
+    ///     assert_eq!(entry.try_insert("b"), Err((2, "b")));
+    /// }
+    /// assert_eq!(cache.peek(&1), Some(&"a"));
+    /// ```
+    #[inline]
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, (K, V)> {
+        if unlikely(self.cache.pinned_full()) {
+            return Err((self.key, value));
+        }
+        Ok(self.insert(value))
+    }
+
+    /// Insert the value as the MRU entry, also returning the entry evicted to make room.
+    ///
+    /// Evicts like [`insert`](Self::insert), including when every entry is pinned.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::{Entry, TinyLru};
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 2>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    ///
+    /// if let Entry::Vacant(entry) = cache.entry(3) {
+    ///     let (_, evicted) = entry.insert_evict("c");
+    ///     assert_eq!(evicted, Some((1, "a")));
+    /// }
+    /// ```
+    #[inline]
+    pub fn insert_evict(self, value: V) -> (&'a mut V, Option<(K, V)>) {
+        let cache = self.cache;
+        let evicted = if unlikely(cache.pinned_full()) { Some(cache.evict_pinned_lru()) } else { cache.make_room() };
+        cache.insert(self.key, value);
+        // The new entry is always appended and linked as the tail.
+        let tail = cache.tail.to_usize();
+        (cache.store.val_mut(tail), evicted)
+    }
+}
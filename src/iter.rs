@@ -0,0 +1,336 @@
+//! Iterators over a [`TinyLru`](crate::TinyLru).
+//!
+//! All iterators walk the intrusive list from `head` (LRU) to `tail` (MRU) via `next`,
+//! and backwards via `prev` when reversed. They read the same node storage before and
+//! after the spill, so ordering never depends on the physical slot layout.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::layout::{NodeSlices, Nodes, RawNodes};
+use crate::{LinkIndex, TinyLru};
+
+/// Iterator over `(&K, &V)` pairs in LRU → MRU order.
+///
+/// Created by [`TinyLru::iter`](crate::TinyLru::iter).
+pub struct Iter<'a, K, V, I = u16> {
+    store: NodeSlices<'a, K, V, I>,
+    front: I, // Next index yielded from the LRU end
+    back: I,  // Next index yielded from the MRU end
+    len: usize, // Entries left; guards against front/back crossing
+}
+
+impl<'a, K, V, I: LinkIndex> Iter<'a, K, V, I> {
+    #[inline]
+    pub(crate) fn new(store: NodeSlices<'a, K, V, I>, head: I, tail: I) -> Self {
+        Self { store, front: head, back: tail, len: store.len() }
+    }
+}
+
+impl<K, V, I: LinkIndex> Clone for Iter<'_, K, V, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { store: self.store, front: self.front, back: self.back, len: self.len }
+    }
+}
+
+impl<'a, K, V, I: LinkIndex> Iterator for Iter<'a, K, V, I> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let index = self.front.to_usize();
+        self.front = self.store.next(index);
+        self.len -= 1;
+        Some((self.store.key(index), self.store.val(index)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V, I: LinkIndex> DoubleEndedIterator for Iter<'_, K, V, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let index = self.back.to_usize();
+        self.back = self.store.prev(index);
+        self.len -= 1;
+        Some((self.store.key(index), self.store.val(index)))
+    }
+}
+
+impl<K, V, I: LinkIndex> ExactSizeIterator for Iter<'_, K, V, I> {}
+
+impl<K, V, I: LinkIndex> FusedIterator for Iter<'_, K, V, I> {}
+
+/// Iterator over `(&K, &mut V)` pairs in LRU → MRU order.
+///
+/// Created by [`TinyLru::iter_mut`](crate::TinyLru::iter_mut). Iterating does not
+/// change the recency order.
+pub struct IterMut<'a, K, V, I = u16> {
+    // Raw base pointers so that disjoint values can be handed out as `&mut` one at a time.
+    store: RawNodes<K, V, I>,
+    front: I,
+    back: I,
+    len: usize,
+    _marker: PhantomData<&'a mut (K, V)>,
+}
+
+impl<'a, K, V, I: LinkIndex> IterMut<'a, K, V, I> {
+    #[inline]
+    pub(crate) fn new<const N: usize>(store: &'a mut Nodes<K, V, I, N>, head: I, tail: I) -> Self {
+        Self {
+            len: store.len(),
+            store: store.raw_mut(),
+            front: head,
+            back: tail,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V, I: LinkIndex> Iterator for IterMut<'a, K, V, I> {
+    type Item = (&'a K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: `front` is a live index of the borrowed slice, and every node on the list
+        // is yielded at most once (`len` stops the walk before front and back overlap),
+        // so no two returned references alias.
+        let (key, val, _, next) = unsafe { self.store.get(self.front.to_usize()) };
+        self.front = next;
+        self.len -= 1;
+        Some((key, val))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V, I: LinkIndex> DoubleEndedIterator for IterMut<'_, K, V, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: see `next`.
+        let (key, val, prev, _) = unsafe { self.store.get(self.back.to_usize()) };
+        self.back = prev;
+        self.len -= 1;
+        Some((key, val))
+    }
+}
+
+impl<K, V, I: LinkIndex> ExactSizeIterator for IterMut<'_, K, V, I> {}
+
+impl<K, V, I: LinkIndex> FusedIterator for IterMut<'_, K, V, I> {}
+
+/// Iterator over keys in LRU → MRU order.
+///
+/// Created by [`TinyLru::keys`](crate::TinyLru::keys).
+pub struct Keys<'a, K, V, I = u16> {
+    pub(crate) inner: Iter<'a, K, V, I>,
+}
+
+/// Iterator over values in LRU → MRU order.
+///
+/// Created by [`TinyLru::values`](crate::TinyLru::values).
+pub struct Values<'a, K, V, I = u16> {
+    pub(crate) inner: Iter<'a, K, V, I>,
+}
+
+/// Iterator over mutable values in LRU → MRU order.
+///
+/// Created by [`TinyLru::values_mut`](crate::TinyLru::values_mut).
+pub struct ValuesMut<'a, K, V, I = u16> {
+    pub(crate) inner: IterMut<'a, K, V, I>,
+}
+
+impl<K, V, I: LinkIndex> Clone for Keys<'_, K, V, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<K, V, I: LinkIndex> Clone for Values<'_, K, V, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+// Projection adaptors: `Keys`, `Values` and `ValuesMut` forward to the pair iterators.
+macro_rules! project_iter {
+    ($name:ident, $item:ty, |$pair:pat_param| $proj:expr) => {
+        impl<'a, K, V, I: LinkIndex> Iterator for $name<'a, K, V, I> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.inner.next().map(|$pair| $proj)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+        }
+
+        impl<K, V, I: LinkIndex> DoubleEndedIterator for $name<'_, K, V, I> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.inner.next_back().map(|$pair| $proj)
+            }
+        }
+
+        impl<K, V, I: LinkIndex> ExactSizeIterator for $name<'_, K, V, I> {}
+
+        impl<K, V, I: LinkIndex> FusedIterator for $name<'_, K, V, I> {}
+    };
+}
+
+project_iter!(Keys, &'a K, |(k, _)| k);
+project_iter!(Values, &'a V, |(_, v)| v);
+project_iter!(ValuesMut, &'a mut V, |(_, v)| v);
+
+/// Owning iterator over `(K, V)` pairs in LRU → MRU order.
+///
+/// Created by `TinyLru::into_iter`. Entries are moved out of the store by following
+/// the links, with no `swap_remove` or relinking per step.
+pub struct IntoIter<K, V, const N: usize, I: LinkIndex = u16> {
+    // Length is zeroed on construction; nodes are read out of the raw buffer, so a
+    // panic mid-iteration leaks the rest instead of double-dropping.
+    store: Nodes<K, V, I, N>,
+    front: I,
+    back: I,
+    len: usize,
+}
+
+impl<K, V, const N: usize, I: LinkIndex> IntoIter<K, V, N, I> {
+    #[inline]
+    pub(crate) fn new(mut store: Nodes<K, V, I, N>, head: I, tail: I) -> Self {
+        let len = store.len();
+        // SAFETY: every live node is reachable from `head`, and each is read out exactly
+        // once by `next`/`next_back` or by `Drop`.
+        unsafe { store.forget_all() };
+        Self { store, front: head, back: tail, len }
+    }
+
+    /// Hand back the emptied store buffer after dropping the remaining entries.
+    #[inline]
+    pub(crate) fn into_store(mut self) -> Nodes<K, V, I, N> {
+        self.by_ref().for_each(drop);
+        core::mem::take(&mut self.store)
+    }
+}
+
+impl<K, V, const N: usize, I: LinkIndex> Drop for IntoIter<K, V, N, I> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+impl<K, V, const N: usize, I: LinkIndex> Iterator for IntoIter<K, V, N, I> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: `front` is a node on the list that has not been read yet.
+        let node = unsafe { self.store.read_raw(self.front.to_usize()) };
+        self.front = node.next;
+        self.len -= 1;
+        Some((node.key, node.val))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V, const N: usize, I: LinkIndex> DoubleEndedIterator for IntoIter<K, V, N, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: `back` is a node on the list that has not been read yet.
+        let node = unsafe { self.store.read_raw(self.back.to_usize()) };
+        self.back = node.prev;
+        self.len -= 1;
+        Some((node.key, node.val))
+    }
+}
+
+impl<K, V, const N: usize, I: LinkIndex> ExactSizeIterator for IntoIter<K, V, N, I> {}
+
+impl<K, V, const N: usize, I: LinkIndex> FusedIterator for IntoIter<K, V, N, I> {}
+
+/// Draining iterator over `(K, V)` pairs in LRU → MRU order.
+///
+/// Created by [`TinyLru::drain`](crate::TinyLru::drain). The store is moved out of the
+/// cache up front, so the cache is already empty and pre-spill while the iterator is
+/// alive. On drop, any remaining entries are dropped and the (now empty) store buffer
+/// is handed back to the cache for reuse.
+pub struct Drain<'a, K, V, const N: usize, S, I: LinkIndex = u16, L = ()> {
+    cache: &'a mut TinyLru<K, V, N, S, I, L>,
+    // Always `Some` until `drop`.
+    inner: Option<IntoIter<K, V, N, I>>,
+}
+
+impl<'a, K, V, const N: usize, S, I: LinkIndex, L> Drain<'a, K, V, N, S, I, L> {
+    #[inline]
+    pub(crate) fn new(cache: &'a mut TinyLru<K, V, N, S, I, L>, inner: IntoIter<K, V, N, I>) -> Self {
+        Self { cache, inner: Some(inner) }
+    }
+}
+
+impl<K, V, const N: usize, S, I: LinkIndex, L> Drop for Drain<'_, K, V, N, S, I, L> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            self.cache.store = inner.into_store();
+        }
+    }
+}
+
+impl<K, V, const N: usize, S, I: LinkIndex, L> Iterator for Drain<'_, K, V, N, S, I, L> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.as_mut()?.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.as_ref().map_or((0, Some(0)), |inner| inner.size_hint())
+    }
+}
+
+impl<K, V, const N: usize, S, I: LinkIndex, L> DoubleEndedIterator for Drain<'_, K, V, N, S, I, L> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.as_mut()?.next_back()
+    }
+}
+
+impl<K, V, const N: usize, S, I: LinkIndex, L> ExactSizeIterator for Drain<'_, K, V, N, S, I, L> {}
+
+impl<K, V, const N: usize, S, I: LinkIndex, L> FusedIterator for Drain<'_, K, V, N, S, I, L> {}
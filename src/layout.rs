@@ -0,0 +1,528 @@
+//! Node storage layouts.
+//!
+//! By default nodes are stored as an array of [`Node`](crate::Node)s (AoS): key, value
+//! and links side by side. With the `soa` feature they are split into three parallel
+//! columns (keys, values, links), so the pre-spill key scan and list relinking only
+//! touch key and link cache lines, however large `V` is.
+//!
+//! Both layouts expose the same index-based API, used by the cache and its iterators:
+//! - [`Nodes`]: owning, inline-then-spill storage.
+//! - [`NodeSlices`]: shared view, used by [`Iter`](crate::Iter).
+//! - [`RawNodes`]: raw pointers that hand out disjoint `&mut V`, used by
+//!   [`IterMut`](crate::IterMut).
+//! - `LinksMut`: unchecked link access, resolved once per relink.
+
+#[cfg(not(feature = "soa"))]
+pub(crate) use aos::{NodeSlices, Nodes, RawNodes};
+#[cfg(feature = "soa")]
+pub(crate) use soa::{NodeSlices, Nodes, RawNodes};
+
+#[cfg(not(feature = "soa"))]
+mod aos {
+    use core::marker::PhantomData;
+
+    use crate::Node;
+    use crate::store::Store;
+
+    /// Nodes stored whole, one `Node` per slot.
+    #[derive(Clone)]
+    pub(crate) struct Nodes<K, V, I, const N: usize> {
+        store: Store<Node<K, V, I>, N>,
+    }
+
+    impl<K, V, I: Copy, const N: usize> Nodes<K, V, I, N> {
+        #[inline(always)]
+        pub(crate) const fn new() -> Self {
+            Self { store: Store::new() }
+        }
+
+        #[inline(always)]
+        pub(crate) fn len(&self) -> usize {
+            self.store.len()
+        }
+
+        #[inline(always)]
+        pub(crate) fn is_empty(&self) -> bool {
+            self.store.is_empty()
+        }
+
+        #[inline(always)]
+        pub(crate) fn push(&mut self, node: Node<K, V, I>) {
+            self.store.push(node);
+        }
+
+        /// Remove the node at `index`, moving the last node into its place.
+        #[inline(always)]
+        pub(crate) fn swap_remove(&mut self, index: usize) -> Node<K, V, I> {
+            self.store.swap_remove(index)
+        }
+
+        #[inline]
+        pub(crate) fn clear(&mut self) {
+            self.store.clear();
+        }
+
+        /// Move back inline when the nodes fit.
+        #[inline]
+        pub(crate) fn shrink_to_fit(&mut self) {
+            self.store.shrink_to_fit();
+        }
+
+        /// See [`Store::forget_all`].
+        #[inline]
+        pub(crate) unsafe fn forget_all(&mut self) {
+            // SAFETY: upheld by the caller.
+            unsafe { self.store.forget_all() }
+        }
+
+        /// See [`Store::read_raw`].
+        #[inline(always)]
+        pub(crate) unsafe fn read_raw(&self, index: usize) -> Node<K, V, I> {
+            // SAFETY: upheld by the caller.
+            unsafe { self.store.read_raw(index) }
+        }
+
+        #[inline(always)]
+        pub(crate) fn key(&self, index: usize) -> &K {
+            &self.store[index].key
+        }
+
+        /// # Safety
+        /// `index` must be below `len`.
+        #[cfg(feature = "fingerprint")]
+        #[inline(always)]
+        pub(crate) unsafe fn key_unchecked(&self, index: usize) -> &K {
+            // SAFETY: upheld by the caller.
+            unsafe { &self.store.get_unchecked(index).key }
+        }
+
+        /// Index of the first key matching `pred`, scanning slots in order.
+        #[cfg(not(feature = "fingerprint"))]
+        #[inline(always)]
+        pub(crate) fn position_key(&self, mut pred: impl FnMut(&K) -> bool) -> Option<usize> {
+            let nodes: &[Node<K, V, I>] = &self.store;
+            // SAFETY: `i < nodes.len()`; skipping the bounds check keeps the scan tight.
+            (0..nodes.len()).find(|&i| pred(unsafe { &nodes.get_unchecked(i).key }))
+        }
+
+        #[inline(always)]
+        pub(crate) fn val(&self, index: usize) -> &V {
+            &self.store[index].val
+        }
+
+        #[inline(always)]
+        pub(crate) fn val_mut(&mut self, index: usize) -> &mut V {
+            &mut self.store[index].val
+        }
+
+        #[inline(always)]
+        pub(crate) fn next(&self, index: usize) -> I {
+            self.store[index].next
+        }
+
+        #[inline(always)]
+        pub(crate) fn prev(&self, index: usize) -> I {
+            self.store[index].prev
+        }
+
+        #[inline(always)]
+        pub(crate) fn set_next(&mut self, index: usize, next: I) {
+            self.store[index].next = next;
+        }
+
+        #[inline(always)]
+        pub(crate) fn set_prev(&mut self, index: usize, prev: I) {
+            self.store[index].prev = prev;
+        }
+
+        #[inline(always)]
+        pub(crate) fn links_mut(&mut self) -> LinksMut<'_, K, V, I> {
+            LinksMut { nodes: &mut self.store }
+        }
+
+        #[inline(always)]
+        pub(crate) fn slices(&self) -> NodeSlices<'_, K, V, I> {
+            NodeSlices { nodes: &self.store }
+        }
+
+        #[inline(always)]
+        pub(crate) fn raw_mut(&mut self) -> RawNodes<K, V, I> {
+            RawNodes { nodes: self.store.as_mut_ptr(), _marker: PhantomData }
+        }
+    }
+
+    impl<K, V, I: Copy, const N: usize> Default for Nodes<K, V, I, N> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Shared view of the live nodes.
+    pub(crate) struct NodeSlices<'a, K, V, I> {
+        nodes: &'a [Node<K, V, I>],
+    }
+
+    impl<K, V, I> Clone for NodeSlices<'_, K, V, I> {
+        #[inline(always)]
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<K, V, I> Copy for NodeSlices<'_, K, V, I> {}
+
+    impl<'a, K, V, I: Copy> NodeSlices<'a, K, V, I> {
+        #[inline(always)]
+        pub(crate) fn len(self) -> usize {
+            self.nodes.len()
+        }
+
+        #[inline(always)]
+        pub(crate) fn key(self, index: usize) -> &'a K {
+            &self.nodes[index].key
+        }
+
+        #[inline(always)]
+        pub(crate) fn val(self, index: usize) -> &'a V {
+            &self.nodes[index].val
+        }
+
+        #[inline(always)]
+        pub(crate) fn next(self, index: usize) -> I {
+            self.nodes[index].next
+        }
+
+        #[inline(always)]
+        pub(crate) fn prev(self, index: usize) -> I {
+            self.nodes[index].prev
+        }
+    }
+
+    /// Link view of the live nodes, without bounds checks.
+    pub(crate) struct LinksMut<'a, K, V, I> {
+        nodes: &'a mut [Node<K, V, I>],
+    }
+
+    impl<K, V, I: Copy> LinksMut<'_, K, V, I> {
+        /// Returns `(prev, next)`.
+        ///
+        /// # Safety
+        /// `index` must be below `len`.
+        #[inline(always)]
+        pub(crate) unsafe fn get(&self, index: usize) -> (I, I) {
+            // SAFETY: upheld by the caller.
+            let node = unsafe { self.nodes.get_unchecked(index) };
+            (node.prev, node.next)
+        }
+
+        /// # Safety
+        /// `index` must be below `len`.
+        #[inline(always)]
+        pub(crate) unsafe fn set_next(&mut self, index: usize, next: I) {
+            // SAFETY: upheld by the caller.
+            unsafe { self.nodes.get_unchecked_mut(index).next = next }
+        }
+
+        /// # Safety
+        /// `index` must be below `len`.
+        #[inline(always)]
+        pub(crate) unsafe fn set_prev(&mut self, index: usize, prev: I) {
+            // SAFETY: upheld by the caller.
+            unsafe { self.nodes.get_unchecked_mut(index).prev = prev }
+        }
+    }
+
+    /// Raw base pointer to the nodes, for handing out disjoint `&mut V` one at a time.
+    pub(crate) struct RawNodes<K, V, I> {
+        nodes: *mut Node<K, V, I>,
+        _marker: PhantomData<Node<K, V, I>>,
+    }
+
+    impl<K, V, I: Copy> RawNodes<K, V, I> {
+        /// Returns the key, the value and `(prev, next)` of the node at `index`.
+        ///
+        /// # Safety
+        /// `index` must be a live node of the borrowed storage, and no other reference to
+        /// that node's value may be alive for `'a`.
+        #[inline(always)]
+        pub(crate) unsafe fn get<'a>(&self, index: usize) -> (&'a K, &'a mut V, I, I) {
+            // SAFETY: upheld by the caller.
+            let node = unsafe { &mut *self.nodes.add(index) };
+            (&node.key, &mut node.val, node.prev, node.next)
+        }
+    }
+}
+
+#[cfg(feature = "soa")]
+mod soa {
+    use core::marker::PhantomData;
+
+    use crate::Node;
+    use crate::store::Store;
+
+    /// List links for one slot.
+    #[derive(Clone, Copy)]
+    struct Link<I> {
+        next: I,
+        prev: I,
+    }
+
+    /// Nodes split into parallel key, value and link columns.
+    ///
+    /// The three columns always have the same length, so they spill and move back
+    /// inline together.
+    #[derive(Clone)]
+    pub(crate) struct Nodes<K, V, I, const N: usize> {
+        keys: Store<K, N>,
+        vals: Store<V, N>,
+        links: Store<Link<I>, N>,
+    }
+
+    impl<K, V, I: Copy, const N: usize> Nodes<K, V, I, N> {
+        #[inline(always)]
+        pub(crate) const fn new() -> Self {
+            Self { keys: Store::new(), vals: Store::new(), links: Store::new() }
+        }
+
+        #[inline(always)]
+        pub(crate) fn len(&self) -> usize {
+            self.links.len()
+        }
+
+        #[inline(always)]
+        pub(crate) fn is_empty(&self) -> bool {
+            self.links.is_empty()
+        }
+
+        #[inline(always)]
+        pub(crate) fn push(&mut self, node: Node<K, V, I>) {
+            self.keys.push(node.key);
+            self.vals.push(node.val);
+            self.links.push(Link { next: node.next, prev: node.prev });
+        }
+
+        /// Remove the node at `index`, moving the last node into its place.
+        #[inline(always)]
+        pub(crate) fn swap_remove(&mut self, index: usize) -> Node<K, V, I> {
+            let Link { next, prev } = self.links.swap_remove(index);
+            Node { key: self.keys.swap_remove(index), val: self.vals.swap_remove(index), next, prev }
+        }
+
+        #[inline]
+        pub(crate) fn clear(&mut self) {
+            self.links.clear();
+            self.keys.clear();
+            self.vals.clear();
+        }
+
+        /// Move back inline when the nodes fit.
+        #[inline]
+        pub(crate) fn shrink_to_fit(&mut self) {
+            self.keys.shrink_to_fit();
+            self.vals.shrink_to_fit();
+            self.links.shrink_to_fit();
+        }
+
+        /// See [`Store::forget_all`].
+        #[inline]
+        pub(crate) unsafe fn forget_all(&mut self) {
+            // SAFETY: upheld by the caller, for every column.
+            unsafe {
+                self.keys.forget_all();
+                self.vals.forget_all();
+                self.links.forget_all();
+            }
+        }
+
+        /// See [`Store::read_raw`].
+        #[inline(always)]
+        pub(crate) unsafe fn read_raw(&self, index: usize) -> Node<K, V, I> {
+            // SAFETY: upheld by the caller; the columns are hidden and read in lockstep.
+            unsafe {
+                let Link { next, prev } = self.links.read_raw(index);
+                Node { key: self.keys.read_raw(index), val: self.vals.read_raw(index), next, prev }
+            }
+        }
+
+        #[inline(always)]
+        pub(crate) fn key(&self, index: usize) -> &K {
+            &self.keys[index]
+        }
+
+        /// # Safety
+        /// `index` must be below `len`.
+        #[cfg(feature = "fingerprint")]
+        #[inline(always)]
+        pub(crate) unsafe fn key_unchecked(&self, index: usize) -> &K {
+            // SAFETY: upheld by the caller.
+            unsafe { self.keys.get_unchecked(index) }
+        }
+
+        /// Index of the first key matching `pred`, scanning slots in order.
+        #[cfg(not(feature = "fingerprint"))]
+        #[inline(always)]
+        pub(crate) fn position_key(&self, pred: impl FnMut(&K) -> bool) -> Option<usize> {
+            self.keys.iter().position(pred)
+        }
+
+        #[inline(always)]
+        pub(crate) fn val(&self, index: usize) -> &V {
+            &self.vals[index]
+        }
+
+        #[inline(always)]
+        pub(crate) fn val_mut(&mut self, index: usize) -> &mut V {
+            &mut self.vals[index]
+        }
+
+        #[inline(always)]
+        pub(crate) fn next(&self, index: usize) -> I {
+            self.links[index].next
+        }
+
+        #[inline(always)]
+        pub(crate) fn prev(&self, index: usize) -> I {
+            self.links[index].prev
+        }
+
+        #[inline(always)]
+        pub(crate) fn set_next(&mut self, index: usize, next: I) {
+            self.links[index].next = next;
+        }
+
+        #[inline(always)]
+        pub(crate) fn set_prev(&mut self, index: usize, prev: I) {
+            self.links[index].prev = prev;
+        }
+
+        #[inline(always)]
+        pub(crate) fn links_mut(&mut self) -> LinksMut<'_, K, V, I> {
+            LinksMut { links: &mut self.links, _marker: PhantomData }
+        }
+
+        #[inline(always)]
+        pub(crate) fn slices(&self) -> NodeSlices<'_, K, V, I> {
+            NodeSlices { keys: &self.keys, vals: &self.vals, links: &self.links }
+        }
+
+        #[inline(always)]
+        pub(crate) fn raw_mut(&mut self) -> RawNodes<K, V, I> {
+            RawNodes {
+                keys: self.keys.as_ptr(),
+                vals: self.vals.as_mut_ptr(),
+                links: self.links.as_ptr(),
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    impl<K, V, I: Copy, const N: usize> Default for Nodes<K, V, I, N> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Shared view of the live columns.
+    pub(crate) struct NodeSlices<'a, K, V, I> {
+        keys: &'a [K],
+        vals: &'a [V],
+        links: &'a [Link<I>],
+    }
+
+    impl<K, V, I> Clone for NodeSlices<'_, K, V, I> {
+        #[inline(always)]
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<K, V, I> Copy for NodeSlices<'_, K, V, I> {}
+
+    impl<'a, K, V, I: Copy> NodeSlices<'a, K, V, I> {
+        #[inline(always)]
+        pub(crate) fn len(self) -> usize {
+            self.links.len()
+        }
+
+        #[inline(always)]
+        pub(crate) fn key(self, index: usize) -> &'a K {
+            &self.keys[index]
+        }
+
+        #[inline(always)]
+        pub(crate) fn val(self, index: usize) -> &'a V {
+            &self.vals[index]
+        }
+
+        #[inline(always)]
+        pub(crate) fn next(self, index: usize) -> I {
+            self.links[index].next
+        }
+
+        #[inline(always)]
+        pub(crate) fn prev(self, index: usize) -> I {
+            self.links[index].prev
+        }
+    }
+
+    /// Link column view, without bounds checks.
+    pub(crate) struct LinksMut<'a, K, V, I> {
+        links: &'a mut [Link<I>],
+        _marker: PhantomData<(K, V)>,
+    }
+
+    impl<K, V, I: Copy> LinksMut<'_, K, V, I> {
+        /// Returns `(prev, next)`.
+        ///
+        /// # Safety
+        /// `index` must be below `len`.
+        #[inline(always)]
+        pub(crate) unsafe fn get(&self, index: usize) -> (I, I) {
+            // SAFETY: upheld by the caller.
+            let link = unsafe { self.links.get_unchecked(index) };
+            (link.prev, link.next)
+        }
+
+        /// # Safety
+        /// `index` must be below `len`.
+        #[inline(always)]
+        pub(crate) unsafe fn set_next(&mut self, index: usize, next: I) {
+            // SAFETY: upheld by the caller.
+            unsafe { self.links.get_unchecked_mut(index).next = next }
+        }
+
+        /// # Safety
+        /// `index` must be below `len`.
+        #[inline(always)]
+        pub(crate) unsafe fn set_prev(&mut self, index: usize, prev: I) {
+            // SAFETY: upheld by the caller.
+            unsafe { self.links.get_unchecked_mut(index).prev = prev }
+        }
+    }
+
+    /// Raw column base pointers, for handing out disjoint `&mut V` one at a time.
+    pub(crate) struct RawNodes<K, V, I> {
+        keys: *const K,
+        vals: *mut V,
+        links: *const Link<I>,
+        _marker: PhantomData<(K, V, I)>,
+    }
+
+    impl<K, V, I: Copy> RawNodes<K, V, I> {
+        /// Returns the key, the value and `(prev, next)` of the node at `index`.
+        ///
+        /// # Safety
+        /// `index` must be a live node of the borrowed storage, and no other reference to
+        /// that node's value may be alive for `'a`.
+        #[inline(always)]
+        pub(crate) unsafe fn get<'a>(&self, index: usize) -> (&'a K, &'a mut V, I, I) {
+            // SAFETY: upheld by the caller.
+            unsafe {
+                let link = *self.links.add(index);
+                (&*self.keys.add(index), &mut *self.vals.add(index), link.prev, link.next)
+            }
+        }
+    }
+}
@@ -4,30 +4,100 @@
 
 extern crate alloc;
 
-use core::hash::Hash;
-use tinyvec::TinyVec;
-use likely_stable::unlikely;
+#[cfg(feature = "admission")]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use hashbrown::HashTable;
+use likely_stable::{likely, unlikely};
 
-/// Maximum capacity for v1 implementation (u16::MAX - 1)
-const MAX_CAPACITY: u16 = u16::MAX - 1;
+#[cfg(feature = "admission")]
+mod admission;
+mod arc;
+mod clock;
+mod entry;
+mod iter;
+mod layout;
+mod link;
+mod listener;
+mod pin;
+mod sieve;
+mod slot_index;
+mod slru;
+mod stats;
+mod store;
+mod ttl;
+mod weighted;
+#[cfg(feature = "fingerprint")]
+mod tags;
+#[cfg(feature = "admission")]
+pub use admission::TinyLfu;
+pub use arc::ArcCache;
+pub use clock::ClockCache;
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use iter::{Drain, IntoIter, Iter, IterMut, Keys, Values, ValuesMut};
+pub use link::LinkIndex;
+pub use listener::{EvictionListener, EvictionReason};
+#[cfg(feature = "stats")]
+pub use stats::CacheStats;
+pub use rustc_hash::FxBuildHasher;
+pub use sieve::SieveCache;
+pub use slru::SlruCache;
+pub use ttl::{Clock, TtlLru};
+#[cfg(feature = "std")]
+pub use ttl::StdClock;
+pub use weighted::{Weigher, WeightedLru};
 
-/// Intrusive node stored in the TinyVec/heap storage.
+use layout::Nodes;
+
+/// Maximum inline capacity N (the inline store tracks its length as a u16)
+const MAX_INLINE: usize = u16::MAX as usize - 1;
+
+/// Intrusive node stored in the inline/heap storage.
 ///
 /// Contains the key-value pair and doubly-linked list pointers for LRU ordering.
-#[derive(Default, Clone)]
-pub struct Entry<K, V> 
-where
-    K: Default + Clone,
-    V: Default,
-{
+/// With the `soa` feature the fields are stored in separate columns, and a `Node`
+/// only exists while an entry is moved in or out.
+#[derive(Clone)]
+pub struct Node<K, V, I = u16> {
     /// The cache key
     pub key: K,
     /// The cached value
     pub val: V,
-    /// Next entry index in LRU order (u16::MAX if tail)
-    pub next: u16,
-    /// Previous entry index in LRU order (u16::MAX if head)
-    pub prev: u16,
+    /// Next entry index in LRU order (`I::NONE` if tail)
+    pub next: I,
+    /// Previous entry index in LRU order (`I::NONE` if head)
+    pub prev: I,
+}
+
+/// When a spilled [`TinyLru`] moves back to inline storage on its own.
+///
+/// Evaluated after [`pop`](TinyLru::pop) and [`remove`](TinyLru::remove) (including
+/// removal through an [`OccupiedEntry`]). Unspilling drops the heap store and the hash
+/// index, so lookups go back to the inline linear scan.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnspillPolicy {
+    /// Stay spilled until `clear`, `drain`, `resize` or an explicit `unspill` (default).
+    #[default]
+    Never,
+    /// Unspill as soon as the entries fit in `N`.
+    ///
+    /// A workload hovering around `N` pays for a spill on every push past it.
+    Eager,
+    /// Unspill once the size falls to `low`, then stay spilled after the next spill
+    /// until the size has grown back to `high`.
+    ///
+    /// The gap between `low` and `N` absorbs small oscillations, and `high` keeps a
+    /// cache that keeps crossing the boundary from rebuilding its index every time.
+    Hysteresis {
+        /// Size at or below which the cache unspills; must be `<= N`.
+        low: usize,
+        /// Size a spilled cache must reach before it may unspill again; must be `> N`
+        /// and `<=` the capacity, or the cache could never re-arm. Lowering the capacity
+        /// below it lowers it to the new capacity.
+        high: usize,
+    },
 }
 
 /// LRU cache with inline-then-spill storage.
@@ -35,6 +105,25 @@ where
 /// For small working sets (≤ N), entries are stored inline on the stack for maximum performance.
 /// Once capacity exceeds N, it transparently spills to heap-backed storage with O(1) operations.
 ///
+/// `S` is the hasher used by the post-spill index. The default, [`FxBuildHasher`], is very
+/// fast but not HashDoS-resistant; supply another `BuildHasher` via
+/// [`with_hasher`](Self::with_hasher) when keys come from untrusted input.
+///
+/// `I` is the [`LinkIndex`] width used for links and index records. The default `u16`
+/// allows capacities up to 65,534; use `u8` for the most compact nodes or `u32` for
+/// caches of hundreds of thousands of entries.
+///
+/// Nodes are stored whole by default. The `soa` feature stores keys, values and links in
+/// separate columns instead, so lookups never pull values into cache when `V` is large;
+/// the API is the same either way.
+///
+/// A cache stays spilled once it has spilled, unless an [`UnspillPolicy`] is set with
+/// [`set_unspill_policy`](Self::set_unspill_policy).
+///
+/// `L` is an [`EvictionListener`] that receives entries the cache drops on its own, such
+/// as LRU evictions inside `push`. The default `()` ignores them at no cost; attach one
+/// with [`with_listener`](Self::with_listener).
+///
 /// # Example
 /// ```
 /// use tiny_lru::TinyLru;
@@ -45,41 +134,84 @@ where
 /// 
 /// assert_eq!(cache.get(&1), Some(&"value"));
 /// assert_eq!(cache.len(), 2);
+///
+/// // Large spilled cache with 32-bit links
+/// let mut big = TinyLru::<u64, u64, 8, tiny_lru::FxBuildHasher, u32>::with_capacity(200_000);
+/// for i in 0..200_000 {
+///     big.push(i, i);
+/// }
+/// assert_eq!(big.len(), 200_000);
 /// ```
 #[derive(Clone)]
-pub struct TinyLru<K, V, const N: usize>
-where
-    K: PartialEq + Hash + Default + Clone,
-    V: Default,
-{
+pub struct TinyLru<K, V, const N: usize, S = FxBuildHasher, I = u16, L = ()> {
     // Unified node storage; starts inline, spills to heap as capacity grows.
+    // Whole nodes by default, or key/value/link columns with the `soa` feature.
     // Compact: no holes; deletions swap the last element into the freed index.
-    store: TinyVec<[Entry<K, V>; N]>,
+    // Inline slots are `MaybeUninit`, so neither K nor V needs `Default`.
+    store: Nodes<K, V, I, N>,
 
+    // One-byte hash tag per inline slot, parallel to `store`; only valid pre-spill.
+    #[cfg(feature = "fingerprint")]
+    tags: [u8; N],
 
     // LRU linkage heads (indices into `store`)
-    head: u16, // LRU index; sentinel if empty
-    tail: u16, // MRU index; sentinel if empty
+    head: I, // LRU index; sentinel if empty
+    tail: I, // MRU index; sentinel if empty
 
-    // Key → index map. Lazily allocated ONLY on first spill to avoid heap allocs pre-spill.
-    // Pre-spill lookups use linear scan over the compact TinyVec.
-    index: Option<rustc_hash::FxHashMap<K, u16>>,
+    // Hash → slot index table. Lazily allocated ONLY on first spill to avoid heap allocs pre-spill.
+    // Stores only slot ids; equality is checked against `store[slot].key`, so keys are never cloned.
+    // Pre-spill lookups use linear scan over the compact inline store.
+    index: Option<HashTable<I>>,
 
-    // Capacity semantics (v1 cap):
-    // - store.len() and capacity are u16; maximum capacity <= 65,534 (u16::MAX - 1)
+    // Capacity semantics:
+    // - store.len() and capacity fit in I; maximum capacity is I::MAX_CAPACITY (I::MAX - 1)
     // - set_capacity requires new_cap > store.len() and new_cap >= N
-    capacity: u16,
+    // - resize/resize_evict only require new_cap >= N; they evict LRU entries to fit
+    capacity: I,
+
+    // Hasher for the post-spill index; unused pre-spill.
+    hash_builder: S,
+
+    // Automatic unspill after pop/remove. `unspill_armed` is only ever false under
+    // `Hysteresis`, between an automatic unspill and regrowing to the high watermark.
+    unspill_policy: UnspillPolicy,
+    unspill_armed: bool,
+
+    // Operation counters; zero-sized and inert without the `stats` feature.
+    counters: stats::Counters,
+
+    // Receives entries the cache drops itself; `()` by default.
+    listener: L,
+
+    // Slots skipped by eviction: inline flags for slots below `N`, heap bits past it.
+    pins: pin::Pins<N>,
+
+    // Optional TinyLFU gate on evicting pushes (`admission` feature); boxed so an
+    // unattached filter costs one word.
+    #[cfg(feature = "admission")]
+    admission: Option<Box<TinyLfu>>,
 }
 
-// Compile-time assertion: N must be <= MAX_CAPACITY
-const fn assert_capacity_limit<const N: usize>() {
-    assert!(N <= MAX_CAPACITY as usize, "N must be <= MAX_CAPACITY for v1 capacity limits");
+/// Hash a key (or a borrowed form of it) for the post-spill index.
+///
+/// `Borrow` guarantees `K` and `Q` hash identically, so probes by `&Q` find keys
+/// inserted as `K`.
+#[inline(always)]
+fn make_hash<S: BuildHasher, Q: Hash + ?Sized>(hash_builder: &S, key: &Q) -> u64 {
+    hash_builder.hash_one(key)
+}
+
+// Compile-time assertion: N must fit both the inline store and the link width
+const fn assert_capacity_limit<const N: usize, I: LinkIndex>() {
+    assert!(N <= MAX_INLINE, "N must be <= MAX_INLINE");
+    assert!(N <= I::MAX_CAPACITY, "N must be <= I::MAX_CAPACITY");
 }
 
-impl<K, V, const N: usize> TinyLru<K, V, N>
+impl<K, V, const N: usize, I, L> TinyLru<K, V, N, FxBuildHasher, I, L>
 where
-    K: Eq + Hash + Default + Clone,
-    V: Default,
+    K: Eq + Hash,
+    I: LinkIndex,
+    L: EvictionListener<K, V> + Default,
 {
     /// Create a new cache with capacity = N.
     ///
@@ -92,21 +224,13 @@ where
     /// ```
     #[inline]
     pub fn new() -> Self {
-        assert_capacity_limit::<N>();
-        
-        Self {
-            store: TinyVec::new(),
-            head: u16::MAX, // Sentinel value for empty list
-            tail: u16::MAX, // Sentinel value for empty list
-            index: None,    // No HashMap allocated pre-spill
-            capacity: N as u16,
-        }
+        Self::with_hasher(FxBuildHasher)
     }
 
     /// Create a new cache with specified capacity (must be >= N).
     ///
     /// # Panics
-    /// Panics if `cap < N`.
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
     ///
     /// # Example
     /// ```
@@ -116,25 +240,151 @@ where
     /// assert_eq!(cache.capacity(), 16);
     /// ```
     #[inline]
-    pub fn with_capacity(cap: u16) -> Self {
-        assert_capacity_limit::<N>();
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::with_capacity_and_hasher(cap, FxBuildHasher)
+    }
+}
+
+impl<K, V, const N: usize, S, I, L> TinyLru<K, V, N, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    /// Create a new cache with capacity = N, using `hash_builder` for the post-spill index.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache: TinyLru<String, u32, 4, RandomState> = TinyLru::with_hasher(RandomState::new());
+    /// cache.push("untrusted".to_string(), 1);
+    /// assert_eq!(cache.get("untrusted"), Some(&1));
+    /// ```
+    #[inline]
+    pub fn with_hasher(hash_builder: S) -> Self
+    where
+        L: Default,
+    {
+        Self::with_capacity_and_hasher(N, hash_builder)
+    }
+
+    /// Create a new cache with specified capacity (must be >= N), using `hash_builder`
+    /// for the post-spill index.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let cache: TinyLru<u64, u64, 4, RandomState> =
+    ///     TinyLru::with_capacity_and_hasher(64, RandomState::new());
+    /// assert_eq!(cache.capacity(), 64);
+    /// ```
+    #[inline]
+    pub fn with_capacity_and_hasher(cap: usize, hash_builder: S) -> Self
+    where
+        L: Default,
+    {
+        assert_capacity_limit::<N, I>();
         
-        // Runtime assertion: capacity must be >= N
-        assert!(cap >= N as u16, "capacity must be >= N");
+        // Runtime assertions: N <= capacity <= I::MAX_CAPACITY
+        assert!(cap >= N, "capacity must be >= N");
+        assert!(cap <= I::MAX_CAPACITY, "capacity must be <= I::MAX_CAPACITY");
         
         Self {
-            store: TinyVec::new(),
-            head: u16::MAX, // Sentinel value for empty list
-            tail: u16::MAX, // Sentinel value for empty list
-            index: None,    // No HashMap allocated pre-spill
-            capacity: cap,
+            store: Nodes::new(),
+            #[cfg(feature = "fingerprint")]
+            tags: [0; N],
+            head: I::NONE, // Sentinel value for empty list
+            tail: I::NONE, // Sentinel value for empty list
+            index: None,   // No HashMap allocated pre-spill
+            capacity: I::from_usize(cap),
+            hash_builder,
+            unspill_policy: UnspillPolicy::Never,
+            unspill_armed: true,
+            counters: stats::Counters::new(),
+            listener: L::default(),
+            pins: pin::Pins::new(),
+            #[cfg(feature = "admission")]
+            admission: None,
+        }
+    }
+
+    /// Returns a reference to the cache's `BuildHasher`.
+    #[inline]
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Replace the [`EvictionListener`], keeping all entries and settings.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::{EvictionReason, TinyLru};
+    ///
+    /// let mut evicted = 0;
+    /// let mut cache = TinyLru::<i32, i32, 2>::new().with_listener(|_, _, reason| {
+    ///     if reason == EvictionReason::Capacity {
+    ///         evicted += 1;
+    ///     }
+    /// });
+    /// for i in 0..5 {
+    ///     cache.push(i, i);
+    /// }
+    /// drop(cache);
+    /// assert_eq!(evicted, 3);
+    /// ```
+    #[inline]
+    pub fn with_listener<L2>(self, listener: L2) -> TinyLru<K, V, N, S, I, L2>
+    where
+        L2: EvictionListener<K, V>,
+    {
+        TinyLru {
+            store: self.store,
+            #[cfg(feature = "fingerprint")]
+            tags: self.tags,
+            head: self.head,
+            tail: self.tail,
+            index: self.index,
+            capacity: self.capacity,
+            hash_builder: self.hash_builder,
+            unspill_policy: self.unspill_policy,
+            unspill_armed: self.unspill_armed,
+            counters: self.counters,
+            listener,
+            pins: self.pins,
+            #[cfg(feature = "admission")]
+            admission: self.admission,
         }
     }
 
+    /// Returns a reference to the [`EvictionListener`].
+    #[inline]
+    pub fn listener(&self) -> &L {
+        &self.listener
+    }
+
+    /// Returns a mutable reference to the [`EvictionListener`].
+    #[inline]
+    pub fn listener_mut(&mut self) -> &mut L {
+        &mut self.listener
+    }
+
     /// Insert or update a key-value pair, promoting to MRU on hit.
     ///
     /// If the key already exists, updates the value and promotes to most recently used.
     /// If the cache is at capacity, removes the least recently used entry first.
+    /// The replaced or evicted pair goes to the [`EvictionListener`].
+    ///
+    /// A new key that cannot get room because every entry is [pinned](Self::pin), or that
+    /// the admission filter (see `set_admission`) turns away, is not inserted; it goes to
+    /// the listener as [`EvictionReason::Rejected`].
     ///
     /// # Example
     /// ```
@@ -149,28 +399,128 @@ where
     /// ```
     #[inline]
     pub fn push(&mut self, key: K, value: V) {
-        // If key exists: update value and promote to MRU
+        self.record_access(&key);
         if let Some(index) = self.find_key_index(&key) {
-            // Update the value
-            self.store[index].val = value;
-            // Promote to MRU (move to tail)
-            self.promote_to_mru(index);
+            let old = self.update_at(index, value);
+            self.listener.on_evict(key, old, EvictionReason::Replaced);
             return;
         }
 
-        if unlikely(self.store.len() == N && self.capacity > N as u16) { 
-            self.spill();
+        if unlikely(self.pinned_full() || !self.admits(&key)) {
+            self.listener.on_evict(key, value, EvictionReason::Rejected);
+            return;
+        }
+        self.insert_notify(key, value);
+    }
+
+    /// Insert or update a key-value pair, returning the displaced pair.
+    ///
+    /// - If the key already exists, the value is replaced and the entry promoted to MRU;
+    ///   returns the passed key together with the old value.
+    /// - If a new key is inserted into a full cache, the LRU entry is evicted and returned.
+    /// - If a new key cannot get room because every entry is [pinned](Self::pin), or the
+    ///   admission filter (see `set_admission`) turns it away, the pushed pair itself is
+    ///   returned and the cache is unchanged.
+    /// - Otherwise returns `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 2>::new();
+    /// assert_eq!(cache.push_evict(1, "a"), None);
+    /// assert_eq!(cache.push_evict(2, "b"), None);
+    /// assert_eq!(cache.push_evict(1, "a2"), Some((1, "a"))); // Update: old value
+    /// assert_eq!(cache.push_evict(3, "c"), Some((2, "b")));  // Eviction: LRU pair
+    /// ```
+    #[inline]
+    pub fn push_evict(&mut self, key: K, value: V) -> Option<(K, V)> {
+        self.record_access(&key);
+        // If key exists: update value and promote to MRU
+        if let Some(index) = self.find_key_index(&key) {
+            let old = self.update_at(index, value);
+            return Some((key, old));
+        }
+
+        if unlikely(self.pinned_full() || !self.admits(&key)) {
+            return Some((key, value));
+        }
+        let evicted = self.make_room();
+        self.insert(key, value);
+        evicted
+    }
+
+    /// Insert or update a key-value pair without ever evicting.
+    ///
+    /// - `Ok(Some(old))` if the key existed; the value is replaced and promoted to MRU.
+    /// - `Ok(None)` if the key was inserted (spilling to the heap if needed).
+    /// - `Err((key, value))` if the key is new and the cache is at capacity; the pair is
+    ///   handed back untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 1>::new();
+    /// assert_eq!(cache.try_push(1, "a"), Ok(None));
+    /// assert_eq!(cache.try_push(1, "b"), Ok(Some("a")));
+    /// assert_eq!(cache.try_push(2, "c"), Err((2, "c")));
+    /// assert_eq!(cache.peek(&1), Some(&"b"));
+    /// ```
+    #[inline]
+    pub fn try_push(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        self.record_access(&key);
+        if let Some(index) = self.find_key_index(&key) {
+            return Ok(Some(self.update_at(index, value)));
+        }
+
+        if unlikely(self.store.len() >= self.capacity.to_usize()) {
+            return Err((key, value));
+        }
+
+        // Has room, so this only spills and never evicts.
+        self.make_room();
+        self.insert(key, value);
+        Ok(None)
+    }
+
+    /// Like [`push_evict`](Self::push_evict), but reports a rejected new key as an error:
+    /// the pair comes back as `Err` when every entry is pinned, or when the
+    /// admission filter (see `set_admission`) turns it away.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 2>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    /// cache.pin(&1);
+    /// assert_eq!(cache.try_push_evict(3, "c"), Ok(Some((2, "b"))));
+    ///
+    /// cache.pin(&3);
+    /// assert_eq!(cache.try_push_evict(4, "d"), Err((4, "d")));
+    /// assert_eq!(cache.try_push_evict(1, "a2"), Ok(Some((1, "a")))); // Updates still work
+    /// ```
+    #[inline]
+    pub fn try_push_evict(&mut self, key: K, value: V) -> Result<Option<(K, V)>, (K, V)> {
+        self.record_access(&key);
+        if let Some(index) = self.find_key_index(&key) {
+            let old = self.update_at(index, value);
+            return Ok(Some((key, old)));
         }
-        if unlikely(self.store.len() >= self.capacity as usize) {
-            self.pop();
+        if unlikely(self.pinned_full() || !self.admits(&key)) {
+            return Err((key, value));
         }
 
+        let evicted = self.make_room();
         self.insert(key, value);
+        Ok(evicted)
     }
 
-    /// Remove and return the least recently used entry.
+    /// Remove and return the least recently used entry that is not pinned.
     ///
-    /// Returns `None` if the cache is empty.
+    /// Returns `None` if the cache is empty or every entry is pinned.
     ///
     /// # Example
     /// ```
@@ -186,60 +536,47 @@ where
     /// ```
     #[inline]
     pub fn pop(&mut self) -> Option<(K, V)> {
-        if self.is_empty() {
-            return None;
-        }
+        let entry = self.pop_lru()?;
+        self.apply_unspill_policy();
+        Some(entry)
+    }
 
-        // Get the LRU index (head)
-        let lru_index = self.head as usize;
-        // Capture next before swap_remove
-        let next_index_before = self.store[lru_index].next;
-        let last_index_before = self.store.len() - 1;
-        
-        // Remove LRU key from index (if post-spill)
-        if self.index.is_some() {
-            let lru_key = self.store[lru_index].key.clone();
-            self.index.as_mut().unwrap().remove(&lru_key);
-        }
-        
-        // Extract the key-value pair before removal
-        let entry = self.store.swap_remove(lru_index);
-        let (key, value) = (entry.key, entry.val);
-        
-        // Handle DLL updates
-        if unlikely(self.store.len() == 0) {
-            // Last element removed - reset to empty state
-            self.head = u16::MAX;
-            self.tail = u16::MAX;
-        } else {
-            // Update head to next element (adjust if it pointed at the old last index)
-            if next_index_before as usize != last_index_before {
-                self.head = next_index_before;
-            }
-            self.store[self.head as usize].prev = u16::MAX;
-            
-            // If we swapped with the last element, update its index in the DLL
-            if lru_index < self.store.len() {
-                self.update_swapped_element_index(lru_index);
-                
-                // Update index for the swapped element (if post-spill)
-                if self.index.is_some() {
-                    // Remove the old key at lru_index (the last element's key)
-                    let swapped_key = self.store[lru_index].key.clone();
-                    self.index.as_mut().unwrap().remove(&swapped_key);
-                    
-                    // Insert the swapped element's key at its new position
-                    self.index.as_mut().unwrap().insert(swapped_key, lru_index as u16);
-                }
+    /// Remove and return the most recently used entry that is not pinned.
+    ///
+    /// Returns `None` if the cache is empty or every entry is pinned. Together with
+    /// `push` this gives stack-like (LIFO) use of the cache.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 4>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    /// cache.get(&1);
+    ///
+    /// assert_eq!(cache.pop_mru(), Some((1, "a")));
+    /// assert_eq!(cache.pop_mru(), Some((2, "b")));
+    /// assert_eq!(cache.pop_mru(), None);
+    /// ```
+    #[inline]
+    pub fn pop_mru(&mut self) -> Option<(K, V)> {
+        let index = if likely(self.pins.is_empty()) {
+            if self.is_empty() {
+                return None;
             }
-        }
-        
-        Some((key, value))
+            self.tail.to_usize()
+        } else {
+            self.first_unpinned(self.tail, false)?
+        };
+        Some(self.remove_at(index))
     }
 
     /// Get a value by key, promoting to MRU on hit.
     ///
-    /// Returns `None` if the key is not found.
+    /// Returns `None` if the key is not found. The key may be any borrowed form of `K`
+    /// (e.g. `&str` for `String` keys), as with `get_mut`, `peek`, `remove` and
+    /// `contains_key`.
     ///
     /// # Example
     /// ```
@@ -250,13 +587,24 @@ where
     /// 
     /// assert_eq!(cache.get(&1), Some(&"value".to_string()));
     /// assert_eq!(cache.get(&2), None);
+    ///
+    /// let mut by_name = TinyLru::<String, i32, 2>::new();
+    /// by_name.push("one".to_string(), 1);
+    /// assert_eq!(by_name.get("one"), Some(&1)); // No `String` allocated to probe
     /// ```
     #[inline]
-    pub fn get(&mut self, key: &K) -> Option<&V> {
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.record_access(key);
         if let Some(index) = self.find_key_index(key) {
+            self.counters.lookup(true);
             self.promote_to_mru(index);
-            Some(&self.store[index].val)
+            Some(self.store.val(index))
         } else {
+            self.counters.lookup(false);
             None
         }
     }
@@ -278,11 +626,18 @@ where
     /// assert_eq!(cache.get(&1), Some(&"value updated".to_string()));
     /// ```
     #[inline]
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.record_access(key);
         if let Some(index) = self.find_key_index(key) {
+            self.counters.lookup(true);
             self.promote_to_mru(index);
-            Some(&mut self.store[index].val)
+            Some(self.store.val_mut(index))
         } else {
+            self.counters.lookup(false);
             None
         }
     }
@@ -305,8 +660,91 @@ where
     /// cache.pop(); // Still removes key 1 (LRU)
     /// ```
     #[inline]
-    pub fn peek(&self, key: &K) -> Option<&V> {
-        self.find_key_index(key).map(|index| &self.store[index].val)
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_key_index(key);
+        self.counters.peek(index.is_some());
+        index.map(|index| self.store.val(index))
+    }
+
+    /// Returns the least recently used entry without changing the order.
+    ///
+    /// This is the LRU end of the list even if it is pinned; `pop` and eviction skip
+    /// pinned entries.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 4>::new();
+    /// assert_eq!(cache.peek_lru(), None);
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    ///
+    /// assert_eq!(cache.peek_lru(), Some((&1, &"a")));
+    /// assert_eq!(cache.peek_mru(), Some((&2, &"b")));
+    /// ```
+    #[inline]
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.peek_slot(self.head)
+    }
+
+    /// Returns the most recently used entry without changing the order.
+    #[inline]
+    pub fn peek_mru(&self) -> Option<(&K, &V)> {
+        self.peek_slot(self.tail)
+    }
+
+    /// Move an entry to the MRU end without reading it. Returns `false` if the key is
+    /// absent.
+    ///
+    /// Unlike `get`, this is not counted as a lookup.
+    #[inline]
+    pub fn promote<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(index) = self.find_key_index(key) else {
+            return false;
+        };
+        self.promote_to_mru(index);
+        true
+    }
+
+    /// Move an entry to the LRU end, making it the next eviction candidate. Returns
+    /// `false` if the key is absent.
+    ///
+    /// Useful for scan resistance: entries known to be used once can be demoted right
+    /// after insertion so they do not push out the working set.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 2>::new();
+    /// cache.push(1, "hot");
+    /// cache.push(2, "scan");
+    /// cache.demote(&2);
+    /// cache.push(3, "scan");  // Evicts 2, not 1
+    ///
+    /// assert!(cache.contains_key(&1));
+    /// assert!(!cache.contains_key(&2));
+    /// ```
+    #[inline]
+    pub fn demote<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(index) = self.find_key_index(key) else {
+            return false;
+        };
+        self.demote_to_lru(index);
+        true
     }
 
     /// Remove a key-value pair and return the owned values.
@@ -326,63 +764,53 @@ where
     /// assert!(cache.is_empty());
     /// ```
     #[inline]
-    pub fn remove(&mut self, key: &K) -> Option<(K, V)> {
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         // Find the key index
         let index = self.find_key_index(key)?;
-        
-        let last_index_before = self.store.len() - 1;
-        let removed_prev = self.store[index].prev;
-        let removed_next = self.store[index].next;
-        
-        // Remove target key from index (if post-spill)
-        if self.index.is_some() {
-            let target_key = self.store[index].key.clone();
-            self.index.as_mut().unwrap().remove(&target_key);
-        }
-        
-        // Extract the key-value pair before removal
-        let entry = self.store.swap_remove(index);
-        let (key, value) = (entry.key, entry.val);
-        
-        // Handle DLL updates
-        if self.store.len() == 0 {
-            // Last element removed - reset to empty state
-            self.head = u16::MAX;
-            self.tail = u16::MAX;
-        } else {
-            // Update DLL pointers. Adjust for swap if needed.
-            let (mut prev, mut next) = (removed_prev, removed_next);
-            if index < last_index_before {
-                // The last element moved into `index`. If removed_prev/next referred to the old last index,
-                // redirect them to `index` now.
-                if prev as usize == last_index_before { prev = index as u16; }
-                if next as usize == last_index_before { next = index as u16; }
-            }
-            self.remove_from_dll(index, prev, next);
+        Some(self.remove_at(index))
+    }
 
-            // If we swapped with the last element, update its index in the DLL
-            if index < self.store.len() {
-                self.update_swapped_element_index(index);
-                
-                // Update index for the swapped element (if post-spill)
-                if self.index.is_some() {
-                    // Remove the old key at index (the last element's key)
-                    let swapped_key = self.store[index].key.clone();
-                    self.index.as_mut().unwrap().remove(&swapped_key);
-                    
-                    // Insert the swapped element's key at its new position
-                    self.index.as_mut().unwrap().insert(swapped_key, index as u16);
-                }
-            }
-        }
-        
-        Some((key, value))
+    /// Remove an entry and hand it to the [`EvictionListener`] instead of returning it.
+    ///
+    /// Returns `true` if the key was present.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::{EvictionReason, TinyLru};
+    ///
+    /// let mut flushed = Vec::new();
+    /// let mut cache = TinyLru::<i32, &str, 2>::new()
+    ///     .with_listener(|k, v, reason| flushed.push((k, v, reason)));
+    /// cache.push(1, "a");
+    ///
+    /// assert!(cache.discard(&1));
+    /// assert!(!cache.discard(&1));
+    /// drop(cache);
+    /// assert_eq!(flushed, [(1, "a", EvictionReason::Removed)]);
+    /// ```
+    #[inline]
+    pub fn discard<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(index) = self.find_key_index(key) else {
+            return false;
+        };
+        let (key, value) = self.remove_at(index);
+        self.listener.on_evict(key, value, EvictionReason::Removed);
+        true
     }
 
     /// Clear all entries from the cache.
     ///
     /// Resets the cache to its initial state, including returning to pre-spill
-    /// mode if it was previously spilled to heap.
+    /// mode if it was previously spilled to heap. Each entry goes to the
+    /// [`EvictionListener`] in LRU → MRU order.
     ///
     /// # Example
     /// ```
@@ -395,21 +823,32 @@ where
     /// ```
     #[inline]
     pub fn clear(&mut self) {
+        if !L::NOOP {
+            let (head, tail) = (self.head, self.tail);
+            let store = core::mem::take(&mut self.store);
+            self.reset_links();
+
+            // Hand entries over in LRU order, then keep the emptied buffer for reuse
+            let mut entries = IntoIter::new(store, head, tail);
+            for (key, value) in &mut entries {
+                self.listener.on_evict(key, value, EvictionReason::Cleared);
+            }
+            self.store = entries.into_store();
+            return;
+        }
+
         // Clear the store efficiently
         self.store.clear();
-        
-        // Reset state to empty
-        self.head = u16::MAX; // Sentinel value for empty list
-        self.tail = u16::MAX; // Sentinel value for empty list
-        
-        // Clear HashMap index - reset to pre-spill state
-        self.index = None;
+        self.reset_links();
     }
 
     /// Adjust the cache capacity.
     ///
     /// # Panics
-    /// Panics if `new_cap <= current_size` or `new_cap < N`.
+    /// Panics if `new_cap <= current_size`, `new_cap < N` or `new_cap > I::MAX_CAPACITY`.
+    ///
+    /// A [`UnspillPolicy::Hysteresis`] high watermark above `new_cap` is lowered to
+    /// `new_cap`, so the policy can still re-arm.
     ///
     /// # Example
     /// ```
@@ -420,17 +859,97 @@ where
     /// assert_eq!(cache.capacity(), 16);
     /// ```
     #[inline]
-    pub fn set_capacity(&mut self, new_cap: u16) {
+    pub fn set_capacity(&mut self, new_cap: usize) {
         // Validate requirements
-        assert!(new_cap > self.store.len() as u16, "new_cap must be > current size");
-        assert!(new_cap >= N as u16, "new_cap must be >= N");
+        assert!(new_cap > self.store.len(), "new_cap must be > current size");
+        assert!(new_cap >= N, "new_cap must be >= N");
+        assert!(new_cap <= I::MAX_CAPACITY, "new_cap must be <= I::MAX_CAPACITY");
+        self.clamp_unspill_policy(new_cap);
         
         // Pre-spill: just update the capacity field
-        // The TinyVec will handle spill automatically when we exceed N
-        self.capacity = new_cap;
+        // The store will move to the heap automatically when we exceed N
+        self.capacity = I::from_usize(new_cap);
     }
 
-    /// Returns the current number of items in the cache.
+    /// Change the capacity, evicting LRU entries that no longer fit.
+    ///
+    /// Unlike [`set_capacity`](Self::set_capacity), `new_cap` may be below the current
+    /// size. Evicted entries are returned in LRU → MRU order, skipping pinned entries.
+    /// If the remaining entries fit in `N`, the cache moves back to inline storage and
+    /// drops its index.
+    ///
+    /// # Panics
+    /// Panics if `new_cap < N`, `new_cap > I::MAX_CAPACITY` or `new_cap` is below the
+    /// number of pinned entries.
+    ///
+    /// A [`UnspillPolicy::Hysteresis`] high watermark above `new_cap` is lowered to
+    /// `new_cap`, as with [`set_capacity`](Self::set_capacity).
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 2>::with_capacity(4);
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    /// cache.push(3, "c"); // Spills
+    ///
+    /// assert_eq!(cache.resize(2), vec![(1, "a")]);
+    /// assert_eq!(cache.capacity(), 2);
+    /// assert!(!cache.is_spilled()); // Back inline
+    /// ```
+    #[inline]
+    pub fn resize(&mut self, new_cap: usize) -> Vec<(K, V)> {
+        let mut evicted = Vec::new();
+        self.resize_evict(new_cap, |key, value| evicted.push((key, value)));
+        evicted
+    }
+
+    /// Change the capacity, passing each evicted entry to `on_evict` instead of
+    /// collecting them.
+    ///
+    /// Same eviction and unspill behavior as [`resize`](Self::resize), without
+    /// allocating for the evicted entries.
+    ///
+    /// # Panics
+    /// Panics if `new_cap < N`, `new_cap > I::MAX_CAPACITY` or `new_cap` is below the
+    /// number of pinned entries.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, u64, 2>::with_capacity(8);
+    /// for i in 0..8 {
+    ///     cache.push(i, 100);
+    /// }
+    ///
+    /// let mut freed = 0;
+    /// cache.resize_evict(3, |_, bytes| freed += bytes);
+    /// assert_eq!(freed, 500);
+    /// assert_eq!(cache.len(), 3);
+    /// ```
+    pub fn resize_evict<F>(&mut self, new_cap: usize, mut on_evict: F)
+    where
+        F: FnMut(K, V),
+    {
+        assert!(new_cap >= N, "new_cap must be >= N");
+        assert!(new_cap <= I::MAX_CAPACITY, "new_cap must be <= I::MAX_CAPACITY");
+        assert!(new_cap >= self.pins.len(), "new_cap must be >= the number of pinned entries");
+        self.clamp_unspill_policy(new_cap);
+
+        while self.store.len() > new_cap {
+            let (key, value) = self.pop_lru().expect("cache is non-empty while over capacity");
+            self.counters.evict();
+            on_evict(key, value);
+        }
+        self.capacity = I::from_usize(new_cap);
+
+        // Shrunk back into the inline range: drop the heap store and the index
+        self.unspill();
+    }
+
+    /// Returns the current number of items in the cache.
     ///
     /// # Example
     /// ```
@@ -442,8 +961,8 @@ where
     /// assert_eq!(cache.len(), 1);
     /// ```
     #[inline]
-    pub fn len(&self) -> u16 {
-        self.store.len() as u16
+    pub fn len(&self) -> usize {
+        self.store.len()
     }
 
     /// Returns `true` if the cache contains no items.
@@ -472,8 +991,8 @@ where
     /// assert_eq!(cache.capacity(), 8);
     /// ```
     #[inline]
-    pub fn capacity(&self) -> u16 {
-        self.capacity
+    pub fn capacity(&self) -> usize {
+        self.capacity.to_usize()
     }
 
     /// Returns `true` if the cache contains the specified key.
@@ -489,7 +1008,11 @@ where
     /// assert!(!cache.contains_key(&2));
     /// ```
     #[inline]
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.find_key_index(key).is_some()
     }
 
@@ -559,63 +1082,667 @@ where
             return false;
         }
 
-        // Let tinyvec handle the storage transition
+        // Move the nodes back into the inline buffer
         self.store.shrink_to_fit();
 
         // Clear HashMap index to return to pre-spill state
         self.index = None;
+        self.counters.unspill();
+
+        // Tags went stale while spilled; rebuild them for the inline scan
+        #[cfg(feature = "fingerprint")]
+        for (i, tag) in self.tags[..self.store.len()].iter_mut().enumerate() {
+            *tag = tags::tag(make_hash(&self.hash_builder, self.store.key(i)));
+        }
+
+        true
+    }
+
+    /// Returns the current automatic unspill policy.
+    #[inline]
+    pub fn unspill_policy(&self) -> UnspillPolicy {
+        self.unspill_policy
+    }
+
+    /// Set when the cache moves back to inline storage on its own after `pop` or
+    /// `remove`. The policy is also evaluated once immediately.
+    ///
+    /// # Panics
+    /// Panics if a [`UnspillPolicy::Hysteresis`] has `low > N`, `high <= N` or `high`
+    /// above the capacity.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::{TinyLru, UnspillPolicy};
+    ///
+    /// let mut cache = TinyLru::<i32, i32, 4>::with_capacity(16);
+    /// cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 8 });
+    /// for i in 0..6 {
+    ///     cache.push(i, i);
+    /// }
+    /// assert!(cache.is_spilled());
+    ///
+    /// cache.pop();
+    /// cache.pop();
+    /// cache.pop();
+    /// assert!(cache.is_spilled()); // 3 entries: fits in N, but above `low`
+    /// cache.pop();
+    /// assert!(!cache.is_spilled()); // Fell to `low`
+    /// ```
+    #[inline]
+    pub fn set_unspill_policy(&mut self, policy: UnspillPolicy) {
+        if let UnspillPolicy::Hysteresis { low, high } = policy {
+            assert!(low <= N, "low watermark must be <= N");
+            assert!(high > N, "high watermark must be > N");
+            assert!(high <= self.capacity.to_usize(), "high watermark must be <= capacity");
+        }
+        self.unspill_policy = policy;
+        self.unspill_armed = true;
+        self.apply_unspill_policy();
+    }
 
+    /// Pin an entry so that eviction skips it. Returns `false` if the key is absent.
+    ///
+    /// Pinned entries still count toward capacity and still move in LRU order on access;
+    /// `push`, `pop` and `resize` pass over them when walking from the LRU end.
+    /// `remove`, `clear` and `drain` remove them as usual. Once every entry of a full
+    /// cache is pinned, pushed keys are rejected rather than inserted; a vacant
+    /// [`Entry`] insert evicts the LRU entry regardless, unless made with
+    /// [`VacantEntry::try_insert`].
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<&str, u32, 2>::new();
+    /// cache.push("config", 1);
+    /// cache.pin(&"config");
+    /// cache.push("a", 2);
+    /// cache.push("b", 3); // Evicts "a", not the older "config"
+    ///
+    /// assert!(cache.contains_key(&"config"));
+    /// assert_eq!(cache.pop(), Some(("b", 3)));
+    /// assert_eq!(cache.pop(), None); // Only pinned entries left
+    /// ```
+    #[inline]
+    pub fn pin<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(index) = self.find_key_index(key) else {
+            return false;
+        };
+        self.pins.insert(index);
         true
     }
 
+    /// Unpin an entry. Returns `false` if the key is absent or was not pinned.
+    #[inline]
+    pub fn unpin<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_key_index(key).is_some_and(|index| self.pins.remove(index))
+    }
+
+    /// Returns `true` if the key is present and pinned.
+    #[inline]
+    pub fn is_pinned<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_key_index(key).is_some_and(|index| self.pins.contains(index))
+    }
+
+    /// Returns the number of pinned entries.
+    #[inline]
+    pub fn pinned_len(&self) -> usize {
+        self.pins.len()
+    }
+
+    /// Attach a TinyLFU admission filter (`admission` feature), or detach it with `None` (the default).
+    ///
+    /// With a filter, `get`, `get_mut` and the `push` methods record every access to a
+    /// key. When a new key would evict the LRU entry, it is only admitted if its
+    /// estimated frequency is higher than the victim's. A rejected pair goes to the
+    /// [`EvictionListener`] as [`EvictionReason::Rejected`] from `push`, and is handed
+    /// back by `push_evict` and `try_push_evict`. Inserts through a vacant
+    /// [`Entry`] and pushes into a cache with room are never filtered.
+    ///
+    /// The filter keeps its history across [`clear`](Self::clear); its
+    /// [`admitted`](TinyLfu::admitted) and [`rejected`](TinyLfu::rejected) counts
+    /// report the decisions made so far.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::{TinyLfu, TinyLru};
+    ///
+    /// let mut cache = TinyLru::<u32, &str, 2>::new();
+    /// cache.set_admission(Some(TinyLfu::new(2)));
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    ///
+    /// // A one-off key does not displace anything
+    /// assert_eq!(cache.push_evict(3, "c"), Some((3, "c")));
+    ///
+    /// // Seen again: now hotter than the victim, which is evicted
+    /// assert_eq!(cache.push_evict(3, "c"), Some((1, "a")));
+    /// assert_eq!(cache.admission().map(|f| (f.admitted(), f.rejected())), Some((1, 1)));
+    /// ```
+    #[cfg(feature = "admission")]
+    #[inline]
+    pub fn set_admission(&mut self, filter: Option<TinyLfu>) {
+        self.admission = filter.map(Box::new);
+    }
+
+    /// Returns the admission filter, if one is attached.
+    #[cfg(feature = "admission")]
+    #[inline]
+    pub fn admission(&self) -> Option<&TinyLfu> {
+        self.admission.as_deref()
+    }
+
+    /// Returns a snapshot of the operation counters (`stats` feature).
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, i32, 2>::with_capacity(3);
+    /// cache.push(1, 1);
+    /// cache.push(2, 2);
+    /// cache.push(3, 3); // Spills
+    /// cache.push(4, 4); // Evicts 1
+    /// cache.get(&1);
+    /// cache.get(&4);
+    ///
+    /// let stats = cache.stats();
+    /// assert_eq!((stats.hits, stats.misses), (1, 1));
+    /// assert_eq!((stats.inserts, stats.evictions, stats.spills), (4, 1, 1));
+    /// ```
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> CacheStats {
+        self.counters.snapshot()
+    }
+
+    /// Zero all operation counters (`stats` feature), e.g. at the start of a
+    /// measurement window.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.counters = stats::Counters::new();
+    }
+
+    /// Get the entry for a key for in-place manipulation, with a single lookup.
+    ///
+    /// An occupied entry is promoted to MRU, as with `get`. Inserting through a vacant
+    /// entry reuses the miss, spilling or evicting the LRU entry only when needed.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<&str, u32, 4>::new();
+    /// *cache.entry("hits").or_insert(0) += 1;
+    /// *cache.entry("hits").or_insert(0) += 1;
+    /// cache.entry("hits").and_modify(|v| *v *= 10).or_insert(0);
+    ///
+    /// assert_eq!(cache.peek(&"hits"), Some(&20));
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N, S, I, L> {
+        match self.find_key_index(&key) {
+            Some(index) => {
+                self.counters.lookup(true);
+                self.promote_to_mru(index);
+                Entry::Occupied(OccupiedEntry::new(self, index))
+            }
+            None => {
+                self.counters.lookup(false);
+                Entry::Vacant(VacantEntry::new(self, key))
+            }
+        }
+    }
+
+    /// Returns an iterator over `(&K, &V)` pairs in LRU → MRU order.
+    ///
+    /// Does not affect the LRU ordering. Use `.rev()` to walk from MRU to LRU.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 4>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    /// cache.get(&1); // 1 becomes MRU
+    ///
+    /// let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    /// assert_eq!(order, [2, 1]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V, I> {
+        Iter::new(self.store.slices(), self.head, self.tail)
+    }
+
+    /// Returns an iterator over `(&K, &mut V)` pairs in LRU → MRU order.
+    ///
+    /// Does not affect the LRU ordering.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, i32, 4>::new();
+    /// cache.push(1, 10);
+    /// cache.push(2, 20);
+    ///
+    /// for (_, val) in cache.iter_mut() {
+    ///     *val += 1;
+    /// }
+    /// assert_eq!(cache.peek(&1), Some(&11));
+    /// assert_eq!(cache.peek(&2), Some(&21));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, I> {
+        IterMut::new(&mut self.store, self.head, self.tail)
+    }
+
+    /// Returns an iterator over keys in LRU → MRU order.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 4>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    ///
+    /// assert_eq!(cache.keys().copied().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V, I> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Returns an iterator over values in LRU → MRU order.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 4>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    ///
+    /// assert_eq!(cache.values().rev().copied().collect::<Vec<_>>(), ["b", "a"]);
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V, I> {
+        Values { inner: self.iter() }
+    }
+
+    /// Returns an iterator over mutable values in LRU → MRU order.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, String, 4>::new();
+    /// cache.push(1, "a".to_string());
+    ///
+    /// for val in cache.values_mut() {
+    ///     val.push('!');
+    /// }
+    /// assert_eq!(cache.peek(&1), Some(&"a!".to_string()));
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V, I> {
+        ValuesMut { inner: self.iter_mut() }
+    }
+
+    /// Remove all entries, yielding them as owned `(K, V)` pairs in LRU → MRU order.
+    ///
+    /// The cache is left empty in its pre-spill state (no index), keeping its storage
+    /// buffer for reuse. Entries not consumed before the iterator is dropped are dropped
+    /// with it.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 4>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    ///
+    /// let drained: Vec<_> = cache.drain().collect();
+    /// assert_eq!(drained, [(1, "a"), (2, "b")]);
+    /// assert!(cache.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, K, V, N, S, I, L> {
+        let store = core::mem::take(&mut self.store);
+        let inner = IntoIter::new(store, self.head, self.tail);
+
+        // Reset to pre-spill state up front; the store buffer is returned on drop.
+        self.reset_links();
+
+        Drain::new(self, inner)
+    }
+
+    /// Remove all entries, yielding them as owned `(K, V)` pairs in MRU → LRU order.
+    ///
+    /// Same as [`drain`](Self::drain) but starting from the most recently used entry.
+    ///
+    /// # Example
+    /// ```
+    /// use tiny_lru::TinyLru;
+    ///
+    /// let mut cache = TinyLru::<i32, &str, 4>::new();
+    /// cache.push(1, "a");
+    /// cache.push(2, "b");
+    ///
+    /// let drained: Vec<_> = cache.drain_mru().collect();
+    /// assert_eq!(drained, [(2, "b"), (1, "a")]);
+    /// ```
+    #[inline]
+    pub fn drain_mru(&mut self) -> core::iter::Rev<Drain<'_, K, V, N, S, I, L>> {
+        self.drain().rev()
+    }
+
     /// Find the index of a key.
-    /// - Pre-spill: linear scan over compact TinyVec
-    /// - Post-spill: O(1) hashmap index lookup
+    /// - Pre-spill: linear scan over compact inline store
+    /// - Post-spill: O(1) hash table lookup
+    ///
     /// Returns None if key not found.
     #[inline(always)]
-    fn find_key_index(&self, key: &K) -> Option<usize> {
+    fn find_key_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         if let Some(index) = &self.index {
-            // Post-spill: look up via hashmap index
-            index.get(key).map(|&idx| idx as usize)
+            // Post-spill: probe by hash, confirm against the stored key
+            let store = &self.store;
+            slot_index::find(index, &self.hash_builder, key, |i| store.key(i))
         } else {
-            // Pre-spill: use raw slice iteration
-            let entries = &self.store[..self.store.len()];
-            for i in 0..entries.len() {
-                // SAFETY: We iterate over `0..entries.len()` where `entries` is a slice of length
-                // `self.size as usize`. Since `i` is guaranteed to be in bounds of this slice,
-                // `get_unchecked(i)` is safe. This avoids bounds checks in the hot path.
-                let entry = unsafe { entries.get_unchecked(i) };
-                if entry.key == *key {
-                    return Some(i);
-                }
-            }
-            None
+            self.scan_inline(key)
         }
     }
 
+    /// Pre-spill lookup: linear scan over the compact inline store.
+    #[cfg(not(feature = "fingerprint"))]
+    #[inline(always)]
+    fn scan_inline<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // Pre-spill: compare keys slot by slot over the inline slice
+        self.store.position_key(|k| k.borrow() == key)
+    }
+
+    /// Pre-spill lookup: scan the tag array, comparing full keys only on tag matches.
+    #[cfg(feature = "fingerprint")]
+    #[inline(always)]
+    fn scan_inline<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let store = &self.store;
+        let tag = tags::tag(make_hash(&self.hash_builder, key));
+        tags::find(&self.tags[..store.len()], tag, |i| {
+            // SAFETY: `find` only yields indices below `tags.len() == store.len()`.
+            unsafe { store.key_unchecked(i) }.borrow() == key
+        })
+    }
+
     /// Spill to heap.
     #[cold]
     fn spill(&mut self) {
-        self.index = Some(rustc_hash::FxHashMap::default());
-        for i in 0..self.store.len() {
-            self.index.as_mut().unwrap().insert(self.store[i].key.clone(), i as u16);
+        let store = &self.store;
+        self.index = Some(slot_index::build(store.len(), &self.hash_builder, |i| store.key(i)));
+        self.counters.spill();
+    }
+
+    /// Unlink and return the LRU unpinned entry without evaluating the unspill policy.
+    #[inline(always)]
+    fn pop_lru(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            return None;
+        }
+        if unlikely(!self.pins.is_empty()) {
+            let index = self.first_unpinned(self.head, true)?;
+            return Some(self.unlink_at(index));
+        }
+
+        // Get the LRU index (head)
+        let lru_index = self.head.to_usize();
+        // Capture next before swap_remove
+        let next_index_before = self.store.next(lru_index);
+        let last_index_before = self.store.len() - 1;
+        
+        // Remove LRU key from index (if post-spill)
+        if let Some(index) = &mut self.index {
+            let hash = make_hash(&self.hash_builder, self.store.key(lru_index));
+            slot_index::unindex(index, hash, lru_index);
+        }
+        
+        // Extract the key-value pair before removal
+        let entry = self.store.swap_remove(lru_index);
+        let (key, value) = (entry.key, entry.val);
+        
+        // Handle DLL updates
+        if unlikely(self.store.is_empty()) {
+            // Last element removed - reset to empty state
+            self.head = I::NONE;
+            self.tail = I::NONE;
+        } else {
+            // Update head to next element (adjust if it pointed at the old last index)
+            if next_index_before.to_usize() != last_index_before {
+                self.head = next_index_before;
+            }
+            self.store.set_prev(self.head.to_usize(), I::NONE);
+            
+            // If we swapped with the last element, update its index in the DLL
+            if lru_index < self.store.len() {
+                self.update_swapped_element_index(lru_index);
+
+                #[cfg(feature = "fingerprint")]
+                if self.index.is_none() {
+                    self.tags[lru_index] = self.tags[last_index_before];
+                }
+                
+                // Point the swapped element's index record at its new slot (if post-spill)
+                if let Some(index) = &mut self.index {
+                    let hash = make_hash(&self.hash_builder, self.store.key(lru_index));
+                    slot_index::reindex(index, hash, last_index_before, lru_index);
+                }
+            }
+        }
+        
+        Some((key, value))
+    }
+
+    /// Walk from `start` towards the MRU end (or the LRU end) to the first unpinned entry.
+    #[cold]
+    fn first_unpinned(&self, start: I, toward_mru: bool) -> Option<usize> {
+        let mut cursor = start;
+        while cursor != I::NONE {
+            let index = cursor.to_usize();
+            if !self.pins.contains(index) {
+                return Some(index);
+            }
+            cursor = if toward_mru { self.store.next(index) } else { self.store.prev(index) };
+        }
+        None
+    }
+
+    /// Whether a new key cannot get room: the cache is at capacity and every entry is pinned.
+    #[inline(always)]
+    pub(crate) fn pinned_full(&self) -> bool {
+        !self.pins.is_empty() && self.pins.len() >= self.store.len() && self.store.len() >= self.capacity.to_usize()
+    }
+
+    /// Evict the LRU entry even though it is pinned, for inserts that cannot fail.
+    #[cold]
+    pub(crate) fn evict_pinned_lru(&mut self) -> (K, V) {
+        let evicted = self.unlink_at(self.head.to_usize());
+        self.counters.evict();
+        evicted
+    }
+
+    /// Feed an access to the admission filter, if there is one.
+    #[cfg(feature = "admission")]
+    #[inline(always)]
+    fn record_access<Q: Hash + ?Sized>(&mut self, key: &Q) {
+        if let Some(filter) = &mut self.admission {
+            filter.record(make_hash(&self.hash_builder, key));
+        }
+    }
+
+    #[cfg(not(feature = "admission"))]
+    #[inline(always)]
+    fn record_access<Q: Hash + ?Sized>(&mut self, _key: &Q) {}
+
+    /// Whether a new key may be inserted: always, unless an admission filter is
+    /// attached, the cache is full, and the filter rates the eviction victim as hot.
+    #[cfg(feature = "admission")]
+    #[inline(always)]
+    fn admits(&mut self, key: &K) -> bool {
+        if likely(self.admission.is_none()) || self.store.len() < self.capacity.to_usize() {
+            return true;
+        }
+        let victim = if self.pins.is_empty() { Some(self.head.to_usize()) } else { self.first_unpinned(self.head, true) };
+        // Every entry pinned: callers check `pinned_full` first
+        let (Some(victim), Some(filter)) = (victim, &mut self.admission) else {
+            return true;
+        };
+        let hasher = &self.hash_builder;
+        filter.admit(make_hash(hasher, key), make_hash(hasher, self.store.key(victim)))
+    }
+
+    #[cfg(not(feature = "admission"))]
+    #[inline(always)]
+    fn admits(&mut self, _key: &K) -> bool {
+        true
+    }
+
+    /// A capacity below the hysteresis high watermark would leave the cache unable to
+    /// re-arm automatic unspill after the first one, so lower the watermark to
+    /// `new_cap`, re-arming if the cache is already that large.
+    #[inline(always)]
+    fn clamp_unspill_policy(&mut self, new_cap: usize) {
+        if let UnspillPolicy::Hysteresis { low, high } = self.unspill_policy
+            && high > new_cap
+        {
+            self.unspill_policy = UnspillPolicy::Hysteresis { low, high: new_cap };
+            self.unspill_armed |= self.store.len() >= new_cap;
+        }
+    }
+
+    /// Unspill after a removal if the [`UnspillPolicy`] says so.
+    #[inline(always)]
+    fn apply_unspill_policy(&mut self) {
+        if self.index.is_none() {
+            return;
+        }
+        match self.unspill_policy {
+            UnspillPolicy::Never => {}
+            UnspillPolicy::Eager => {
+                self.unspill();
+            }
+            UnspillPolicy::Hysteresis { low, .. } => {
+                if self.unspill_armed && self.store.len() <= low && self.unspill() {
+                    self.unspill_armed = false;
+                }
+            }
+        }
+    }
+
+    /// Replace the value at `index` and promote it to MRU, returning the old value.
+    #[inline(always)]
+    fn update_at(&mut self, index: usize, value: V) -> V {
+        let old = core::mem::replace(self.store.val_mut(index), value);
+        self.promote_to_mru(index);
+        self.counters.update();
+        old
+    }
+
+    /// Insert a new key, handing any entry evicted to make room to the listener.
+    #[inline(always)]
+    pub(crate) fn insert_notify(&mut self, key: K, value: V) {
+        if let Some((old_key, old_value)) = self.make_room() {
+            self.listener.on_evict(old_key, old_value, EvictionReason::Capacity);
+        }
+        self.insert(key, value);
+    }
+
+    /// Empty the list and index, returning to the pre-spill state.
+    #[inline(always)]
+    fn reset_links(&mut self) {
+        self.head = I::NONE; // Sentinel value for empty list
+        self.tail = I::NONE; // Sentinel value for empty list
+        self.index = None;
+        self.unspill_armed = true;
+        self.pins.clear();
+    }
+
+    /// Prepare for inserting a new key: spill if the inline storage is full and the
+    /// capacity allows growth, then evict the LRU entry if still at capacity.
+    ///
+    /// Returns the evicted entry, if any.
+    #[inline(always)]
+    fn make_room(&mut self) -> Option<(K, V)> {
+        if unlikely(self.store.len() == N && self.capacity.to_usize() > N) { 
+            self.spill();
+        }
+        if unlikely(self.store.len() >= self.capacity.to_usize()) {
+            let evicted = self.pop_lru();
+            assert!(evicted.is_some() || self.is_empty(), "cannot make room: every entry is pinned");
+            if evicted.is_some() {
+                self.counters.evict();
+            }
+            return evicted;
         }
+        None
     }
 
     /// Insert a new entry
     #[inline(always)]
     fn insert(&mut self, key: K, value: V) {
         let new_index = self.store.len();
+        self.counters.insert();
         
-        if self.index.is_some() {
-            self.index.as_mut().unwrap().insert(key.clone(), new_index as u16);
+        let (store, hasher) = (&self.store, &self.hash_builder);
+        if let Some(index) = &mut self.index {
+            // The new node is not in `store` yet, but rehashing only touches existing records
+            let hash = make_hash(hasher, &key);
+            slot_index::insert(index, hash, new_index, hasher, |i| store.key(i));
+
+            // Regrown to the high watermark: automatic unspill may happen again
+            if unlikely(!self.unspill_armed)
+                && let UnspillPolicy::Hysteresis { high, .. } = self.unspill_policy
+            {
+                self.unspill_armed = new_index + 1 >= high;
+            }
+        }
+
+        #[cfg(feature = "fingerprint")]
+        if self.index.is_none() {
+            self.tags[new_index] = tags::tag(make_hash(&self.hash_builder, &key));
         }
 
         // Create new entry
-        let new_entry = Entry {
-            key: key, // Clone for the entry
+        let new_entry = Node {
+            key,
             val: value,
-            next: u16::MAX, // Will be set to current tail
+            next: I::NONE, // Will be set to current tail
             prev: self.tail, // Previous MRU
         };
 
@@ -623,47 +1750,94 @@ where
         self.store.push(new_entry);
 
         // Update linked list
-        if self.tail == u16::MAX {
+        if self.tail == I::NONE {
             // First entry - set as both head and tail
-            self.head = new_index as u16;
-            self.tail = new_index as u16;
+            self.head = I::from_usize(new_index);
+            self.tail = I::from_usize(new_index);
         } else {
             // Link to previous tail
-            self.store[self.tail as usize].next = new_index as u16;
-            self.tail = new_index as u16;
+            self.store.set_next(self.tail.to_usize(), I::from_usize(new_index));
+            self.tail = I::from_usize(new_index);
+        }
+
+    }
+
+    /// Key and value at a list end (`head` or `tail`), or `None` if it is the sentinel.
+    #[inline(always)]
+    fn peek_slot(&self, slot: I) -> Option<(&K, &V)> {
+        (slot != I::NONE).then(|| (self.store.key(slot.to_usize()), self.store.val(slot.to_usize())))
+    }
+
+    /// Demote an entry to LRU (move to head).
+    #[inline(always)]
+    fn demote_to_lru(&mut self, index: usize) {
+        if self.store.len() <= 1 || index == self.head.to_usize() {
+            return;
+        }
+
+        // Not the head, so `prev` is a valid index
+        let (prev, next) = (self.store.prev(index), self.store.next(index));
+        self.store.set_next(prev.to_usize(), next);
+        if next != I::NONE {
+            self.store.set_prev(next.to_usize(), prev);
+        } else {
+            self.tail = prev;
+        }
+
+        // Attach in front of the old head
+        let old_head = self.head;
+        self.store.set_prev(old_head.to_usize(), I::from_usize(index));
+        self.store.set_prev(index, I::NONE);
+        self.store.set_next(index, old_head);
+        self.head = I::from_usize(index);
+    }
+
+    /// Move the entry at `index` to sit just before the entry at `at`.
+    #[inline(always)]
+    fn move_before(&mut self, index: usize, at: usize) {
+        if index == at || self.store.next(index).to_usize() == at {
+            return;
         }
+        let (prev, next) = (self.store.prev(index), self.store.next(index));
+        self.remove_from_dll(index, prev, next);
 
+        let before = self.store.prev(at);
+        self.store.set_prev(at, I::from_usize(index));
+        self.store.set_next(index, I::from_usize(at));
+        self.store.set_prev(index, before);
+        if before == I::NONE {
+            self.head = I::from_usize(index);
+        } else {
+            self.store.set_next(before.to_usize(), I::from_usize(index));
+        }
     }
 
     /// Promote an entry to MRU (move to tail).
     #[inline(always)]
     fn promote_to_mru(&mut self, index: usize) {
         // Early return if already MRU or only one element.
-        if self.store.len() <= 1 || index == self.tail as usize {
+        if self.store.len() <= 1 || index == self.tail.to_usize() {
             return;
         }
 
-        let entries: *mut [Entry<K, V>] = self.store.as_mut_slice();
-        let entry_index = index as u16;
+        let mut links = self.store.links_mut();
+        let entry_index = I::from_usize(index);
 
         // SAFETY: `index` is guaranteed to be in bounds by the caller (`get`/`get_mut`).
-        let (prev, next) = unsafe {
-            let entry = (&*entries).get_unchecked(index);
-            (entry.prev, entry.next)
-        };
+        let (prev, next) = unsafe { links.get(index) };
 
         // Detach the entry by linking its neighbors to each other.
-        // SAFETY: All pointer writes are on indices that were part of a valid linked list.
+        // SAFETY: All writes are on indices that were part of a valid linked list.
         // `next` is guaranteed to be a valid index because the entry is not the tail.
         unsafe {
-            (&mut *entries).get_unchecked_mut(next as usize).prev = prev;
+            links.set_prev(next.to_usize(), prev);
         }
 
-        if prev != u16::MAX {
+        if prev != I::NONE {
             // The entry was not the head; update its `prev` neighbor.
             // SAFETY: `prev` is a valid index since it's not the sentinel.
             unsafe {
-                (&mut *entries).get_unchecked_mut(prev as usize).next = next;
+                links.set_next(prev.to_usize(), next);
             }
         } else {
             // The entry was the head; the new head is its `next` neighbor.
@@ -674,32 +1848,95 @@ where
         let old_tail = self.tail;
         // SAFETY: `old_tail` is a valid index because the list has more than one element.
         unsafe {
-            (&mut *entries).get_unchecked_mut(old_tail as usize).next = entry_index;
+            links.set_next(old_tail.to_usize(), entry_index);
         }
         self.tail = entry_index;
 
         // SAFETY: `index` is guaranteed to be in bounds.
         unsafe {
-            let entry = (&mut *entries).get_unchecked_mut(index);
-            entry.prev = old_tail;
-            entry.next = u16::MAX;
+            links.set_prev(index, old_tail);
+            links.set_next(index, I::NONE);
+        }
+    }
+
+    /// Unlink and remove the entry at `index`, returning the owned pair.
+    #[inline(always)]
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        let entry = self.unlink_at(index);
+        self.apply_unspill_policy();
+        entry
+    }
+
+    /// [`remove_at`](Self::remove_at) without evaluating the unspill policy.
+    #[inline(always)]
+    fn unlink_at(&mut self, index: usize) -> (K, V) {
+        let last_index_before = self.store.len() - 1;
+        let removed_prev = self.store.prev(index);
+        let removed_next = self.store.next(index);
+        
+        // Remove target key from index (if post-spill)
+        if let Some(map) = &mut self.index {
+            let hash = make_hash(&self.hash_builder, self.store.key(index));
+            slot_index::unindex(map, hash, index);
+        }
+        
+        // Extract the key-value pair before removal
+        let entry = self.store.swap_remove(index);
+        let (key, value) = (entry.key, entry.val);
+        
+        // Handle DLL updates
+        if self.store.is_empty() {
+            // Last element removed - reset to empty state
+            self.head = I::NONE;
+            self.tail = I::NONE;
+        } else {
+            // Update DLL pointers. Adjust for swap if needed.
+            let (mut prev, mut next) = (removed_prev, removed_next);
+            if index < last_index_before {
+                // The last element moved into `index`. If removed_prev/next referred to the old last index,
+                // redirect them to `index` now.
+                if prev.to_usize() == last_index_before { prev = I::from_usize(index); }
+                if next.to_usize() == last_index_before { next = I::from_usize(index); }
+            }
+            self.remove_from_dll(index, prev, next);
+
+            // If we swapped with the last element, update its index in the DLL
+            if index < self.store.len() {
+                self.update_swapped_element_index(index);
+
+                #[cfg(feature = "fingerprint")]
+                if self.index.is_none() {
+                    self.tags[index] = self.tags[last_index_before];
+                }
+                
+                // Point the swapped element's index record at its new slot (if post-spill)
+                if let Some(map) = &mut self.index {
+                    let hash = make_hash(&self.hash_builder, self.store.key(index));
+                    slot_index::reindex(map, hash, last_index_before, index);
+                }
+            }
+        }
+
+        if unlikely(!self.pins.is_empty()) {
+            self.pins.swap_remove(index, last_index_before);
         }
+        (key, value)
     }
 
     /// Remove a node from the doubly-linked list.
     #[inline(always)]
-    fn remove_from_dll(&mut self, _index: usize, prev: u16, next: u16) {
+    fn remove_from_dll(&mut self, _index: usize, prev: I, next: I) {
         // Update previous node's next pointer
-        if prev != u16::MAX {
-            self.store[prev as usize].next = next;
+        if prev != I::NONE {
+            self.store.set_next(prev.to_usize(), next);
         } else {
             // This was the head - update head
             self.head = next;
         }
         
         // Update next node's prev pointer
-        if next != u16::MAX {
-            self.store[next as usize].prev = prev;
+        if next != I::NONE {
+            self.store.set_prev(next.to_usize(), prev);
         } else {
             // This was the tail - update tail
             self.tail = prev;
@@ -710,29 +1947,81 @@ where
     #[inline(always)]
     fn update_swapped_element_index(&mut self, new_index: usize) {
         // Copy the prev/next values to avoid borrow conflicts
-        let prev = self.store[new_index].prev;
-        let next = self.store[new_index].next;
+        let prev = self.store.prev(new_index);
+        let next = self.store.next(new_index);
         
         // Update references to this element
-        if prev != u16::MAX {
-            self.store[prev as usize].next = new_index as u16;
+        if prev != I::NONE {
+            self.store.set_next(prev.to_usize(), I::from_usize(new_index));
         } else {
             // This is now the head
-            self.head = new_index as u16;
+            self.head = I::from_usize(new_index);
         }
         
-        if next != u16::MAX {
-            self.store[next as usize].prev = new_index as u16;
+        if next != I::NONE {
+            self.store.set_prev(next.to_usize(), I::from_usize(new_index));
         } else {
             // This is now the tail
-            self.tail = new_index as u16;
+            self.tail = I::from_usize(new_index);
         }
     }
 }
 
-// Iterators will be provided later.
-// pub struct Iter<'a, K, V, const N: usize> { /* fields */ }
-// pub struct IterMut<'a, K, V, const N: usize> { /* fields */ }
+impl<K, V, const N: usize, S, I, L> Default for TinyLru<K, V, N, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+    I: LinkIndex,
+    L: EvictionListener<K, V> + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, const N: usize, S, I: LinkIndex, L> IntoIterator for TinyLru<K, V, N, S, I, L> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N, I>;
+
+    /// Consume the cache, yielding owned `(K, V)` pairs in LRU → MRU order.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.store, self.head, self.tail)
+    }
+}
+
+impl<'a, K, V, const N: usize, S, I, L> IntoIterator for &'a TinyLru<K, V, N, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, I>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize, S, I, L> IntoIterator for &'a mut TinyLru<K, V, N, S, I, L>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+    L: EvictionListener<K, V>,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V, I>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,52 @@
+//! Link/index width for the intrusive list and the post-spill index.
+//!
+//! Every `next`/`prev` link, the `head`/`tail` pointers and each post-spill index record
+//! is one `LinkIndex`. The integer's `MAX` is the "no entry" sentinel, so a width
+//! supports capacities up to `MAX - 1`.
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Unsigned integer type used for slot indices and links.
+///
+/// Implemented for `u8` (capacity ≤ 254), `u16` (≤ 65,534, the default) and
+/// `u32` (≤ 4,294,967,294). Narrower types keep nodes smaller; wider types allow
+/// larger spilled caches.
+pub trait LinkIndex: sealed::Sealed + Copy + Eq + core::fmt::Debug + 'static {
+    /// Sentinel marking "no entry" (end of list, empty cache).
+    const NONE: Self;
+    /// Largest capacity representable with this width (one below the sentinel).
+    const MAX_CAPACITY: usize;
+
+    /// Convert a slot index; the caller guarantees `i <= MAX_CAPACITY`.
+    fn from_usize(i: usize) -> Self;
+
+    /// Widen to a slot index.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_link_index {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl LinkIndex for $ty {
+                const NONE: Self = <$ty>::MAX;
+                const MAX_CAPACITY: usize = (<$ty>::MAX - 1) as usize;
+
+                #[inline(always)]
+                fn from_usize(i: usize) -> Self {
+                    i as $ty
+                }
+
+                #[inline(always)]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_link_index!(u8, u16, u32);
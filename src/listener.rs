@@ -0,0 +1,74 @@
+//! Eviction notifications for a [`TinyLru`](crate::TinyLru).
+//!
+//! The listener is a type parameter, so the default `()` listener compiles to nothing.
+//! It only sees entries the cache would otherwise drop itself; methods that hand the
+//! pair back to the caller (`pop`, `remove`, `push_evict`, `resize`, `drain`, ...) do
+//! not notify it.
+
+/// Why an entry left the cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EvictionReason {
+    /// The LRU entry was evicted to make room for a new key (`push`, vacant entry inserts).
+    Capacity,
+    /// The entry was removed explicitly with [`discard`](crate::TinyLru::discard).
+    Removed,
+    /// `push` overwrote an existing key; the listener gets the pushed key and the old value.
+    Replaced,
+    /// The entry was dropped by [`clear`](crate::TinyLru::clear).
+    Cleared,
+    /// `push` offered a new entry that was never cached: every entry of the full cache was
+    /// [pinned](crate::TinyLru::pin), or the admission filter (`admission` feature)
+    /// turned it away.
+    Rejected,
+}
+
+/// Receives the owned key and value of entries dropped by the cache.
+///
+/// Implemented for `()` (ignore everything, the default) and for closures taking
+/// `(K, V, EvictionReason)`.
+///
+/// # Example
+/// ```
+/// use tiny_lru::{EvictionListener, EvictionReason, TinyLru};
+///
+/// #[derive(Default)]
+/// struct Flushed(Vec<(u32, EvictionReason)>);
+///
+/// impl EvictionListener<u32, String> for Flushed {
+///     fn on_evict(&mut self, key: u32, _value: String, reason: EvictionReason) {
+///         self.0.push((key, reason));
+///     }
+/// }
+///
+/// let mut cache: TinyLru<u32, String, 2, tiny_lru::FxBuildHasher, u16, Flushed> = TinyLru::new();
+/// cache.push(1, "a".to_string());
+/// cache.push(2, "b".to_string());
+/// cache.push(3, "c".to_string());
+/// assert_eq!(cache.listener().0, [(1, EvictionReason::Capacity)]);
+/// ```
+pub trait EvictionListener<K, V> {
+    /// `true` only for listeners that ignore every call, such as `()`.
+    ///
+    /// Lets `clear` drop entries in place instead of visiting them one by one.
+    const NOOP: bool = false;
+
+    /// Called with an entry the cache is dropping, and why.
+    fn on_evict(&mut self, key: K, value: V, reason: EvictionReason);
+}
+
+impl<K, V> EvictionListener<K, V> for () {
+    const NOOP: bool = true;
+
+    #[inline(always)]
+    fn on_evict(&mut self, _key: K, _value: V, _reason: EvictionReason) {}
+}
+
+impl<K, V, F> EvictionListener<K, V> for F
+where
+    F: FnMut(K, V, EvictionReason),
+{
+    #[inline]
+    fn on_evict(&mut self, key: K, value: V, reason: EvictionReason) {
+        self(key, value, reason)
+    }
+}
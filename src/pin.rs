@@ -0,0 +1,119 @@
+//! Pinned-slot bookkeeping for [`TinyLru::pin`](crate::TinyLru::pin).
+//!
+//! One flag per inline slot, kept inline, plus one bit per spilled slot on the heap,
+//! allocated on the first pin past `N`; pinning never allocates before the cache
+//! spills. Slots move when `swap_remove` fills a hole with the last node, so the flag
+//! moves with it. While nothing is pinned the set is empty and the eviction path takes
+//! its usual head-only route.
+
+use alloc::vec::Vec;
+
+#[derive(Clone)]
+pub(crate) struct Pins<const N: usize> {
+    // Slots `0..N`.
+    inline: [bool; N],
+    // Slots `N..`, one bit each; empty until a spilled slot is pinned.
+    spilled: Vec<u64>,
+    count: usize,
+}
+
+impl<const N: usize> Pins<N> {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self { inline: [false; N], spilled: Vec::new(), count: 0 }
+    }
+
+    /// Number of pinned slots.
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.count
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, slot: usize) -> bool {
+        match self.inline.get(slot) {
+            Some(&pinned) => pinned,
+            None => {
+                let slot = slot - N;
+                self.spilled.get(slot / 64).is_some_and(|word| word & (1 << (slot % 64)) != 0)
+            }
+        }
+    }
+
+    /// Pin `slot`; returns `false` if it was already pinned.
+    pub(crate) fn insert(&mut self, slot: usize) -> bool {
+        let newly = match self.inline.get_mut(slot) {
+            Some(pinned) => !core::mem::replace(pinned, true),
+            None => {
+                let slot = slot - N;
+                let word = slot / 64;
+                if word >= self.spilled.len() {
+                    self.spilled.resize(word + 1, 0);
+                }
+                let bit = 1 << (slot % 64);
+                let newly = self.spilled[word] & bit == 0;
+                self.spilled[word] |= bit;
+                newly
+            }
+        };
+        self.count += newly as usize;
+        newly
+    }
+
+    /// Unpin `slot`; returns `false` if it was not pinned.
+    pub(crate) fn remove(&mut self, slot: usize) -> bool {
+        let was = match self.inline.get_mut(slot) {
+            Some(pinned) => core::mem::replace(pinned, false),
+            None => {
+                let slot = slot - N;
+                let Some(word) = self.spilled.get_mut(slot / 64) else {
+                    return false;
+                };
+                let bit = 1 << (slot % 64);
+                let was = *word & bit != 0;
+                *word &= !bit;
+                was
+            }
+        };
+        self.count -= was as usize;
+        was
+    }
+
+    /// Mirror `swap_remove(slot)` on a store whose last slot was `last`: `slot`'s pin
+    /// goes away and `last`'s pin (if any) moves into `slot`.
+    #[inline]
+    pub(crate) fn swap_remove(&mut self, slot: usize, last: usize) {
+        self.remove(slot);
+        if slot != last && self.remove(last) {
+            self.insert(slot);
+        }
+    }
+
+    /// Unpin everything, keeping the allocation.
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        if self.count != 0 {
+            self.inline = [false; N];
+            self.spilled.fill(0);
+            self.count = 0;
+        }
+    }
+
+    /// Whether the spilled-slot bits have been allocated.
+    #[cfg(test)]
+    pub(crate) fn has_heap(&self) -> bool {
+        self.spilled.capacity() != 0
+    }
+}
+
+impl<const N: usize> Default for Pins<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
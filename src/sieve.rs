@@ -0,0 +1,269 @@
+//! SIEVE eviction on top of [`TinyLru`](crate::TinyLru)'s storage and links.
+//!
+//! The list keeps insertion order (head = oldest) and is never reordered by hits; a hit
+//! only sets the entry's visited bit. To evict, a hand walks from where it last stopped
+//! towards newer entries, clearing visited bits until it reaches an unvisited entry,
+//! which is removed. Past the newest entry the hand wraps back to the oldest.
+//!
+//! See Zhang et al., "SIEVE is Simpler than LRU" (NSDI '24).
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+use crate::{FxBuildHasher, LinkIndex, TinyLru};
+
+/// A value and its SIEVE visited bit.
+#[derive(Clone)]
+pub(crate) struct Visited<V> {
+    value: V,
+    pub(crate) visited: bool,
+}
+
+/// Cache with SIEVE eviction: hits cost one store instead of an LRU relink.
+///
+/// Same inline-then-spill storage and API shape as [`TinyLru`]. Eviction keeps
+/// entries that were hit since the hand last passed them, so hot keys survive one-off
+/// scans much like in an LRU, but `get` never touches the list links.
+///
+/// # Example
+/// ```
+/// use tiny_lru::SieveCache;
+///
+/// let mut cache = SieveCache::<u32, &str, 3>::new();
+/// cache.push(1, "a");
+/// cache.push(2, "b");
+/// cache.push(3, "c");
+/// cache.get(&1);
+///
+/// cache.push(4, "d"); // Hand skips visited 1, evicts 2
+/// assert!(cache.contains_key(&1));
+/// assert!(!cache.contains_key(&2));
+/// ```
+#[derive(Clone)]
+pub struct SieveCache<K, V, const N: usize, S = FxBuildHasher, I = u16> {
+    pub(crate) cache: TinyLru<K, Visited<V>, N, S, I>,
+
+    // Next entry the hand examines; `I::NONE` means start from the oldest (head).
+    pub(crate) hand: I,
+}
+
+impl<K, V, const N: usize, I> SieveCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    /// Create a cache with capacity = N.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(N)
+    }
+
+    /// Create a cache with the given capacity (must be >= N).
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::with_capacity_and_hasher(cap, FxBuildHasher)
+    }
+}
+
+impl<K, V, const N: usize, S, I> SieveCache<K, V, N, S, I>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    /// Create a cache with the given capacity (must be >= N), using `hash_builder` for
+    /// the post-spill index.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity_and_hasher(cap: usize, hash_builder: S) -> Self {
+        Self { cache: TinyLru::with_capacity_and_hasher(cap, hash_builder), hand: I::NONE }
+    }
+
+    /// Insert or update a key-value pair.
+    ///
+    /// Updating marks the entry visited. A new key is added unvisited as the newest
+    /// entry, evicting one entry with the hand if the cache is full.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        if let Some(index) = self.cache.find_key_index(&key) {
+            *self.cache.store.val_mut(index) = Visited { value, visited: true };
+            self.cache.counters.update();
+            return;
+        }
+
+        if self.cache.len() >= self.cache.capacity() && !self.cache.is_empty() {
+            let victim = self.sweep();
+            self.unlink(victim);
+            self.cache.counters.evict();
+        }
+        // Room is guaranteed now, so this only spills
+        self.cache.make_room();
+        self.cache.insert(key, Visited { value, visited: false });
+    }
+
+    /// Get a value by key, marking it visited. Does not reorder anything.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Get a mutable reference by key, marking it visited.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.cache.find_key_index(key);
+        self.cache.counters.lookup(index.is_some());
+        let slot = self.cache.store.val_mut(index?);
+        slot.visited = true;
+        Some(&mut slot.value)
+    }
+
+    /// Peek at a value by key without marking it visited.
+    #[inline]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.peek(key).map(|v| &v.value)
+    }
+
+    /// Returns `true` if the key is present. Does not mark it visited.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.contains_key(key)
+    }
+
+    /// Evict and return the entry the hand selects next, clearing visited bits on the
+    /// way as a full-cache `push` would.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.cache.is_empty() {
+            return None;
+        }
+        let victim = self.sweep();
+        let (key, v) = self.unlink(victim);
+        Some((key, v.value))
+    }
+
+    /// Remove a key-value pair and return it.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.cache.find_key_index(key)?;
+        let (key, v) = self.unlink(index);
+        Some((key, v.value))
+    }
+
+    /// Remove all entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hand = I::NONE;
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if the cache holds no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the maximum number of entries.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+
+    /// Returns `true` if the entries have spilled to the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        self.cache.is_spilled()
+    }
+
+    /// Returns a snapshot of the operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> crate::CacheStats {
+        self.cache.stats()
+    }
+
+    /// Zero all operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.cache.reset_stats();
+    }
+
+    /// Advance the hand to the next unvisited entry, clearing visited bits on the way, and
+    /// return its slot.
+    ///
+    /// Terminates within one lap plus one step: the first lap clears every bit.
+    #[inline]
+    fn sweep(&mut self) -> usize {
+        let mut cursor = if self.hand == I::NONE { self.cache.head } else { self.hand };
+        loop {
+            let index = cursor.to_usize();
+            let slot = self.cache.store.val_mut(index);
+            if !slot.visited {
+                self.hand = cursor;
+                return index;
+            }
+            slot.visited = false;
+            cursor = self.cache.store.next(index);
+            if cursor == I::NONE {
+                cursor = self.cache.head;
+            }
+        }
+    }
+
+    /// Remove the entry at `index`. A hand resting on it moves on to the next newer entry,
+    /// and follows the last slot if `swap_remove` moves it.
+    #[inline(always)]
+    fn unlink(&mut self, index: usize) -> (K, Visited<V>) {
+        let last = self.cache.len() - 1;
+        if index == self.hand.to_usize() {
+            self.hand = self.cache.store.next(index);
+        }
+        // `swap_remove` moves the last slot into `index`
+        if self.hand.to_usize() == last && index != last {
+            self.hand = I::from_usize(index);
+        }
+        self.cache.unlink_at(index)
+    }
+}
+
+impl<K, V, const N: usize, I> Default for SieveCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
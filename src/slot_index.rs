@@ -0,0 +1,84 @@
+//! Post-spill hash index shared by [`TinyLru`](crate::TinyLru) and
+//! [`ClockCache`](crate::ClockCache).
+//!
+//! The table holds only slot ids; keys stay in the store and are reached through a
+//! `key_at` accessor, so records carry no key clone and need no `K: Clone`. Records
+//! are matched by slot id where possible, which skips the key comparison.
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+use hashbrown::HashTable;
+
+use crate::{LinkIndex, make_hash};
+
+/// Build an index over slots `0..len`, with room for one more record.
+#[inline]
+pub(crate) fn build<'a, K, S, I>(len: usize, hasher: &S, key_at: impl Fn(usize) -> &'a K) -> HashTable<I>
+where
+    K: Hash + 'a,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    let mut index = HashTable::with_capacity(len + 1);
+    for slot in 0..len {
+        insert(&mut index, make_hash(hasher, key_at(slot)), slot, hasher, &key_at);
+    }
+    index
+}
+
+/// Probe by hash and confirm against the stored key.
+#[inline(always)]
+pub(crate) fn find<'a, K, Q, S, I>(
+    index: &HashTable<I>,
+    hasher: &S,
+    key: &Q,
+    key_at: impl Fn(usize) -> &'a K,
+) -> Option<usize>
+where
+    K: Borrow<Q> + 'a,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    index.find(make_hash(hasher, key), |&i| key_at(i.to_usize()).borrow() == key).map(|&i| i.to_usize())
+}
+
+/// Add the record for a key with `hash`, stored at `slot`.
+///
+/// `key_at` rehashes existing records if the table grows.
+#[inline(always)]
+pub(crate) fn insert<'a, K, S, I>(
+    index: &mut HashTable<I>,
+    hash: u64,
+    slot: usize,
+    hasher: &S,
+    key_at: impl Fn(usize) -> &'a K,
+) where
+    K: Hash + 'a,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    index.insert_unique(hash, I::from_usize(slot), |&i| make_hash(hasher, key_at(i.to_usize())));
+}
+
+/// Remove the record that points at `slot`.
+///
+/// Matches on the slot id rather than the key, so no key comparison is needed.
+#[inline(always)]
+pub(crate) fn unindex<I: LinkIndex>(index: &mut HashTable<I>, hash: u64, slot: usize) {
+    if let Ok(entry) = index.find_entry(hash, |&i| i.to_usize() == slot) {
+        entry.remove();
+    }
+}
+
+/// Repoint the record for an entry moved from slot `from` to slot `to`.
+///
+/// The key and its hash are unchanged, so the record stays in its bucket and only
+/// the slot id is patched: one probe, no removal or reinsertion.
+#[inline(always)]
+pub(crate) fn reindex<I: LinkIndex>(index: &mut HashTable<I>, hash: u64, from: usize, to: usize) {
+    if let Some(record) = index.find_mut(hash, |&i| i.to_usize() == from) {
+        *record = I::from_usize(to);
+    }
+}
@@ -0,0 +1,356 @@
+//! Segmented LRU on top of [`TinyLru`](crate::TinyLru)'s storage and links.
+//!
+//! Both segments share the one intrusive list, split by a boundary pointer:
+//!
+//! ```text
+//! head                                boundary                 tail
+//!  [ probation LRU ... probation MRU ][ protected LRU ... protected MRU ]
+//! ```
+//!
+//! New keys are linked in just before the boundary. A hit on a probationary entry moves
+//! it to the tail, into the protected segment; when that overflows, the boundary steps
+//! forward and its old node becomes the probation MRU without moving. Eviction always
+//! takes the head, so one-off keys from a scan push each other out before they reach
+//! anything that was hit twice.
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+use crate::{FxBuildHasher, LinkIndex, TinyLru};
+
+/// A value and the segment its node is in.
+#[derive(Clone)]
+pub(crate) struct Segmented<V> {
+    value: V,
+    pub(crate) protected: bool,
+}
+
+/// Scan-resistant LRU with a probationary and a protected segment.
+///
+/// Same inline-then-spill storage as [`TinyLru`]: up to `N` entries live inline with
+/// no heap allocation. `capacity` bounds both segments together; the protected segment
+/// holds at most [`protected_capacity`](Self::protected_capacity) of them.
+///
+/// # Example
+/// ```
+/// use tiny_lru::SlruCache;
+///
+/// let mut cache = SlruCache::<u32, &str, 4>::with_capacity_and_protected(4, 2);
+/// cache.push(1, "hot");
+/// cache.get(&1); // Second touch: protected
+///
+/// for key in 100..110 {
+///     cache.push(key, "scan"); // One-off keys only displace each other
+/// }
+/// assert_eq!(cache.get(&1), Some(&"hot"));
+/// ```
+#[derive(Clone)]
+pub struct SlruCache<K, V, const N: usize, S = FxBuildHasher, I = u16> {
+    pub(crate) cache: TinyLru<K, Segmented<V>, N, S, I>,
+
+    // First protected node; everything before it is probationary. `I::NONE` if the
+    // protected segment is empty.
+    pub(crate) boundary: I,
+    protected_len: usize,
+    protected_cap: usize,
+}
+
+impl<K, V, const N: usize, I> SlruCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    /// Create a cache with capacity = N and the default protected share (80%).
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(N)
+    }
+
+    /// Create a cache with the given capacity (must be >= N) and the default protected
+    /// share (80%).
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::with_capacity_and_protected(cap, cap * 4 / 5)
+    }
+
+    /// Create a cache with the given capacity, of which at most `protected` entries
+    /// are protected.
+    ///
+    /// # Panics
+    /// Panics if `cap < N`, `cap > I::MAX_CAPACITY` or `protected > cap`.
+    #[inline]
+    pub fn with_capacity_and_protected(cap: usize, protected: usize) -> Self {
+        Self::with_capacity_protected_and_hasher(cap, protected, FxBuildHasher)
+    }
+}
+
+impl<K, V, const N: usize, S, I> SlruCache<K, V, N, S, I>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    /// Create a cache with the given capacity, protected segment size and post-spill
+    /// hasher.
+    ///
+    /// # Panics
+    /// Panics if `cap < N`, `cap > I::MAX_CAPACITY` or `protected > cap`.
+    #[inline]
+    pub fn with_capacity_protected_and_hasher(cap: usize, protected: usize, hash_builder: S) -> Self {
+        assert!(protected <= cap, "protected must be <= capacity");
+        Self {
+            cache: TinyLru::with_capacity_and_hasher(cap, hash_builder),
+            boundary: I::NONE,
+            protected_len: 0,
+            protected_cap: protected,
+        }
+    }
+
+    /// Insert or update a key-value pair.
+    ///
+    /// A new key enters the probationary segment, evicting the LRU entry if the cache
+    /// is full. Updating an existing key counts as a hit.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        if let Some(index) = self.cache.find_key_index(&key) {
+            self.cache.store.val_mut(index).value = value;
+            self.cache.counters.update();
+            self.touch(index);
+            return;
+        }
+
+        if self.cache.len() >= self.cache.capacity() && !self.cache.is_empty() {
+            self.unlink(self.cache.head.to_usize());
+            self.cache.counters.evict();
+        }
+        // Room is guaranteed now, so this only spills
+        self.cache.make_room();
+        self.cache.insert(key, Segmented { value, protected: false });
+
+        // Appended at the tail; move it to the probation MRU end
+        if self.boundary != I::NONE {
+            self.cache.move_before(self.cache.tail.to_usize(), self.boundary.to_usize());
+        }
+    }
+
+    /// Get a value by key. A hit moves a probationary entry into the protected segment,
+    /// and a protected entry to its MRU end.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_hit(key)?;
+        Some(&self.cache.store.val(index).value)
+    }
+
+    /// Get a mutable reference by key, with the same promotion as [`get`](Self::get).
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_hit(key)?;
+        Some(&mut self.cache.store.val_mut(index).value)
+    }
+
+    /// Peek at a value by key without promoting it.
+    #[inline]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.peek(key).map(|s| &s.value)
+    }
+
+    /// Returns `true` if the key is present. Does not promote.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.contains_key(key)
+    }
+
+    /// Remove and return the next eviction candidate: the probationary LRU entry, or the
+    /// protected LRU entry if probation is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.cache.is_empty() {
+            return None;
+        }
+        let (key, s) = self.unlink(self.cache.head.to_usize());
+        Some((key, s.value))
+    }
+
+    /// Remove a key-value pair and return it.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.cache.find_key_index(key)?;
+        let (key, s) = self.unlink(index);
+        Some((key, s.value))
+    }
+
+    /// Remove all entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.boundary = I::NONE;
+        self.protected_len = 0;
+    }
+
+    /// Change the maximum size of the protected segment, demoting its LRU entries to
+    /// probation if it is now over.
+    ///
+    /// # Panics
+    /// Panics if `protected > capacity`.
+    #[inline]
+    pub fn set_protected_capacity(&mut self, protected: usize) {
+        assert!(protected <= self.cache.capacity(), "protected must be <= capacity");
+        self.protected_cap = protected;
+        self.shrink_protected();
+    }
+
+    /// Returns the maximum size of the protected segment.
+    #[inline]
+    pub fn protected_capacity(&self) -> usize {
+        self.protected_cap
+    }
+
+    /// Returns the number of protected entries.
+    #[inline]
+    pub fn protected_len(&self) -> usize {
+        self.protected_len
+    }
+
+    /// Returns the number of probationary entries.
+    #[inline]
+    pub fn probation_len(&self) -> usize {
+        self.cache.len() - self.protected_len
+    }
+
+    /// Returns the total number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if the cache holds no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the maximum number of entries across both segments.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+
+    /// Returns `true` if the entries have spilled to the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        self.cache.is_spilled()
+    }
+
+    /// Returns a snapshot of the operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> crate::CacheStats {
+        self.cache.stats()
+    }
+
+    /// Zero all operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.cache.reset_stats();
+    }
+
+    /// Look up a key for a promoting access, counting the hit or miss.
+    #[inline(always)]
+    fn find_hit<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.cache.find_key_index(key);
+        self.cache.counters.lookup(index.is_some());
+        let index = index?;
+        self.touch(index);
+        Some(index)
+    }
+
+    /// Record a hit on the entry at `index`: move it to the protected MRU end.
+    #[inline(always)]
+    fn touch(&mut self, index: usize) {
+        let protected = &mut self.cache.store.val_mut(index).protected;
+        if *protected {
+            // Leaving the boundary slot: the next protected node takes over
+            if index == self.boundary.to_usize() && self.cache.store.next(index) != I::NONE {
+                self.boundary = self.cache.store.next(index);
+            }
+            self.cache.promote_to_mru(index);
+            return;
+        }
+
+        *protected = true;
+        self.protected_len += 1;
+        self.cache.promote_to_mru(index);
+        if self.boundary == I::NONE {
+            self.boundary = I::from_usize(index);
+        }
+        self.shrink_protected();
+    }
+
+    /// Demote protected LRU entries until the protected segment fits. They stay where
+    /// they are, as the probation MRU entries, and the boundary moves past them.
+    #[inline(always)]
+    fn shrink_protected(&mut self) {
+        while self.protected_len > self.protected_cap {
+            let index = self.boundary.to_usize();
+            self.cache.store.val_mut(index).protected = false;
+            self.boundary = self.cache.store.next(index);
+            self.protected_len -= 1;
+        }
+    }
+
+    /// Remove the entry at `index`, keeping the boundary and segment sizes in step.
+    #[inline(always)]
+    fn unlink(&mut self, index: usize) -> (K, Segmented<V>) {
+        let last = self.cache.len() - 1;
+        if index == self.boundary.to_usize() {
+            self.boundary = self.cache.store.next(index);
+        }
+        // `swap_remove` moves the last slot into `index`
+        if self.boundary.to_usize() == last && index != last {
+            self.boundary = I::from_usize(index);
+        }
+
+        let (key, s) = self.cache.unlink_at(index);
+        self.protected_len -= s.protected as usize;
+        (key, s)
+    }
+}
+
+impl<K, V, const N: usize, I> Default for SlruCache<K, V, N, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    I: LinkIndex,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,249 @@
+//! Operation counters for sizing a cache (`stats` feature).
+//!
+//! Every [`TinyLru`](crate::TinyLru) carries a `Counters` value. With the `stats` feature it
+//! holds one counter per event; without it, it is a zero-sized type whose methods are empty,
+//! so the calls on the hot paths compile away entirely.
+//!
+//! Counters bumped through `&mut self` use `AtomicU64::get_mut` and cost a plain increment.
+//! Only `peek`, which takes `&self`, uses a relaxed load and store, which keeps the cache
+//! `Sync` without a locked read-modify-write. Concurrent peeks may undercount.
+//!
+//! Targets without 64-bit atomics (e.g. `thumbv6m`, `riscv32imc`) get `Cell<u64>` counters
+//! instead, so there a cache with the `stats` feature is `Send` but not `Sync`.
+
+#[cfg(feature = "stats")]
+pub use enabled::CacheStats;
+#[cfg(feature = "stats")]
+pub(crate) use enabled::Counters;
+#[cfg(not(feature = "stats"))]
+pub(crate) use disabled::Counters;
+
+#[cfg(feature = "stats")]
+mod enabled {
+    #[cfg(not(target_has_atomic = "64"))]
+    use core::cell::Cell;
+    #[cfg(target_has_atomic = "64")]
+    use core::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+    /// A snapshot of a cache's operation counters, from [`TinyLru::stats`](crate::TinyLru::stats).
+    ///
+    /// Counts accumulate from creation or the last
+    /// [`reset_stats`](crate::TinyLru::reset_stats); `clear` and `drain` do not reset them.
+    /// The snapshot is a plain copy: [`reset`](Self::reset) zeroes the copy, while
+    /// `reset_stats` zeroes the live counters in the cache.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct CacheStats {
+        /// Lookups that found the key (`get`, `get_mut`, `peek`, `entry`).
+        pub hits: u64,
+        /// Lookups that did not find the key.
+        pub misses: u64,
+        /// New keys added (`push`, `try_push`, vacant entry inserts).
+        pub inserts: u64,
+        /// Existing keys overwritten by `push` or `try_push`.
+        pub updates: u64,
+        /// Entries dropped to make room for a new key or to fit a smaller capacity.
+        ///
+        /// `pop` and `remove` are explicit and not counted.
+        pub evictions: u64,
+        /// Moves from inline to heap storage.
+        pub spills: u64,
+        /// Moves from heap back to inline storage, explicit or by [`UnspillPolicy`](crate::UnspillPolicy).
+        pub unspills: u64,
+    }
+
+    impl CacheStats {
+        /// Total lookups, `hits + misses`.
+        #[inline]
+        pub fn lookups(&self) -> u64 {
+            self.hits + self.misses
+        }
+
+        /// Fraction of lookups that hit, or `0.0` if there were none.
+        ///
+        /// # Example
+        /// ```
+        /// use tiny_lru::CacheStats;
+        ///
+        /// let stats = CacheStats { hits: 3, misses: 1, ..CacheStats::default() };
+        /// assert_eq!(stats.hit_ratio(), 0.75);
+        /// assert_eq!(CacheStats::default().hit_ratio(), 0.0);
+        /// ```
+        #[inline]
+        pub fn hit_ratio(&self) -> f64 {
+            match self.lookups() {
+                0 => 0.0,
+                n => self.hits as f64 / n as f64,
+            }
+        }
+
+        /// Zero every count in this snapshot, e.g. to reuse it as a running total across
+        /// several caches. The cache's own counters are reset with
+        /// [`reset_stats`](crate::TinyLru::reset_stats).
+        ///
+        /// # Example
+        /// ```
+        /// use tiny_lru::CacheStats;
+        ///
+        /// let mut stats = CacheStats { hits: 3, misses: 1, ..CacheStats::default() };
+        /// stats.reset();
+        /// assert_eq!(stats, CacheStats::default());
+        /// ```
+        #[inline]
+        pub fn reset(&mut self) {
+            *self = Self::default();
+        }
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    #[derive(Default)]
+    struct Counter(AtomicU64);
+
+    #[cfg(target_has_atomic = "64")]
+    impl Counter {
+        #[inline(always)]
+        fn bump(&mut self) {
+            *self.0.get_mut() += 1;
+        }
+
+        // Not `fetch_add`: only `peek` gets here, and a lost update beats a locked op.
+        #[inline(always)]
+        fn bump_shared(&self) {
+            self.0.store(self.0.load(Relaxed) + 1, Relaxed);
+        }
+
+        #[inline(always)]
+        fn get(&self) -> u64 {
+            self.0.load(Relaxed)
+        }
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    impl Clone for Counter {
+        fn clone(&self) -> Self {
+            Counter(AtomicU64::new(self.get()))
+        }
+    }
+
+    #[cfg(not(target_has_atomic = "64"))]
+    #[derive(Clone, Default)]
+    struct Counter(Cell<u64>);
+
+    #[cfg(not(target_has_atomic = "64"))]
+    impl Counter {
+        #[inline(always)]
+        fn bump(&mut self) {
+            *self.0.get_mut() += 1;
+        }
+
+        #[inline(always)]
+        fn bump_shared(&self) {
+            self.0.set(self.0.get() + 1);
+        }
+
+        #[inline(always)]
+        fn get(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    /// Live counters embedded in the cache.
+    #[derive(Clone, Default)]
+    pub(crate) struct Counters {
+        hits: Counter,
+        misses: Counter,
+        inserts: Counter,
+        updates: Counter,
+        evictions: Counter,
+        spills: Counter,
+        unspills: Counter,
+    }
+
+    impl Counters {
+        #[inline]
+        pub(crate) fn new() -> Self {
+            Self::default()
+        }
+
+        #[inline(always)]
+        pub(crate) fn lookup(&mut self, hit: bool) {
+            if hit { self.hits.bump() } else { self.misses.bump() }
+        }
+
+        #[inline(always)]
+        pub(crate) fn peek(&self, hit: bool) {
+            if hit { self.hits.bump_shared() } else { self.misses.bump_shared() }
+        }
+
+        #[inline(always)]
+        pub(crate) fn insert(&mut self) {
+            self.inserts.bump();
+        }
+
+        #[inline(always)]
+        pub(crate) fn update(&mut self) {
+            self.updates.bump();
+        }
+
+        #[inline(always)]
+        pub(crate) fn evict(&mut self) {
+            self.evictions.bump();
+        }
+
+        #[inline(always)]
+        pub(crate) fn spill(&mut self) {
+            self.spills.bump();
+        }
+
+        #[inline(always)]
+        pub(crate) fn unspill(&mut self) {
+            self.unspills.bump();
+        }
+
+        pub(crate) fn snapshot(&self) -> CacheStats {
+            CacheStats {
+                hits: self.hits.get(),
+                misses: self.misses.get(),
+                inserts: self.inserts.get(),
+                updates: self.updates.get(),
+                evictions: self.evictions.get(),
+                spills: self.spills.get(),
+                unspills: self.unspills.get(),
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "stats"))]
+mod disabled {
+    /// Zero-sized stand-in; every method is a no-op.
+    #[derive(Clone)]
+    pub(crate) struct Counters;
+
+    impl Counters {
+        #[inline(always)]
+        pub(crate) fn new() -> Self {
+            Counters
+        }
+
+        #[inline(always)]
+        pub(crate) fn lookup(&mut self, _hit: bool) {}
+
+        #[inline(always)]
+        pub(crate) fn peek(&self, _hit: bool) {}
+
+        #[inline(always)]
+        pub(crate) fn insert(&mut self) {}
+
+        #[inline(always)]
+        pub(crate) fn update(&mut self) {}
+
+        #[inline(always)]
+        pub(crate) fn evict(&mut self) {}
+
+        #[inline(always)]
+        pub(crate) fn spill(&mut self) {}
+
+        #[inline(always)]
+        pub(crate) fn unspill(&mut self) {}
+    }
+}
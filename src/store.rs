@@ -0,0 +1,242 @@
+//! Inline-then-spill node storage.
+//!
+//! A minimal `TinyVec` replacement built on `MaybeUninit`, so node types need no
+//! `Default` impl to fill unused inline slots. Starts as a fixed inline array and moves
+//! to a heap `Vec` on the first push past `N`. Derefs to a slice of the live elements.
+
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+/// Fixed-capacity inline buffer; only the first `len` slots are initialized.
+pub(crate) struct InlineVec<T, const N: usize> {
+    len: u16,
+    buf: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> InlineVec<T, N> {
+    #[inline(always)]
+    const fn new() -> Self {
+        Self { len: 0, buf: [const { MaybeUninit::uninit() }; N] }
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` slots are initialized.
+        unsafe { core::slice::from_raw_parts(self.buf.as_ptr().cast::<T>(), self.len as usize) }
+    }
+
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` slots are initialized.
+        unsafe {
+            core::slice::from_raw_parts_mut(self.buf.as_mut_ptr().cast::<T>(), self.len as usize)
+        }
+    }
+
+    /// Move all elements into a `Vec` with room for `additional` more, leaving `self` empty.
+    #[cold]
+    fn drain_to_vec(&mut self, additional: usize) -> Vec<T> {
+        let len = self.len as usize;
+        let mut vec = Vec::with_capacity(len + additional);
+        // SAFETY: the first `len` slots are initialized and are moved out bitwise;
+        // `len` is reset so they are not dropped again.
+        unsafe {
+            ptr::copy_nonoverlapping(self.buf.as_ptr().cast::<T>(), vec.as_mut_ptr(), len);
+            vec.set_len(len);
+        }
+        self.len = 0;
+        vec
+    }
+}
+
+impl<T, const N: usize> Drop for InlineVec<T, N> {
+    fn drop(&mut self) {
+        // SAFETY: drops exactly the initialized prefix.
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for InlineVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut out = Self::new();
+        for item in self.as_slice() {
+            out.buf[out.len as usize].write(item.clone());
+            // Bump per element so a panicking clone only drops what was written.
+            out.len += 1;
+        }
+        out
+    }
+}
+
+/// Node storage that starts inline and spills to the heap.
+pub(crate) enum Store<T, const N: usize> {
+    Inline(InlineVec<T, N>),
+    Heap(Vec<T>),
+}
+
+impl<T, const N: usize> Store<T, N> {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Store::Inline(InlineVec::new())
+    }
+
+    /// Append an element, moving to the heap if the inline buffer is full.
+    #[inline(always)]
+    pub(crate) fn push(&mut self, value: T) {
+        match self {
+            Store::Inline(inline) if (inline.len as usize) < N => {
+                inline.buf[inline.len as usize].write(value);
+                inline.len += 1;
+            }
+            Store::Inline(inline) => {
+                let mut vec = inline.drain_to_vec(N.max(1));
+                vec.push(value);
+                *self = Store::Heap(vec);
+            }
+            Store::Heap(vec) => vec.push(value),
+        }
+    }
+
+    /// Remove the element at `index`, moving the last element into its place.
+    ///
+    /// # Panics
+    /// Panics if `index >= len`.
+    #[inline(always)]
+    pub(crate) fn swap_remove(&mut self, index: usize) -> T {
+        match self {
+            Store::Inline(inline) => {
+                let len = inline.len as usize;
+                assert!(index < len, "swap_remove index out of bounds");
+                let last = len - 1;
+                // SAFETY: `index` and `last` are initialized; after the read, the last slot is
+                // logically moved into `index` and `len` shrinks past it.
+                unsafe {
+                    let base = inline.buf.as_mut_ptr().cast::<T>();
+                    let value = ptr::read(base.add(index));
+                    ptr::copy(base.add(last), base.add(index), 1);
+                    inline.len -= 1;
+                    value
+                }
+            }
+            Store::Heap(vec) => vec.swap_remove(index),
+        }
+    }
+
+    /// Drop all elements, keeping the current buffer (inline or heap) for reuse.
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        match self {
+            Store::Inline(inline) => {
+                let len = inline.len;
+                inline.len = 0;
+                // SAFETY: the first `len` slots were initialized and `len` is already reset,
+                // so a panicking destructor cannot cause a double drop.
+                unsafe {
+                    let base = inline.buf.as_mut_ptr().cast::<T>();
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base, len as usize));
+                }
+            }
+            Store::Heap(vec) => vec.clear(),
+        }
+    }
+
+    /// Set the length to zero without dropping any element.
+    ///
+    /// # Safety
+    /// Every element must be moved out afterwards with [`read_raw`](Self::read_raw), or be
+    /// deliberately leaked.
+    #[inline]
+    pub(crate) unsafe fn forget_all(&mut self) {
+        match self {
+            Store::Inline(inline) => inline.len = 0,
+            // SAFETY: upheld by the caller.
+            Store::Heap(vec) => unsafe { vec.set_len(0) },
+        }
+    }
+
+    /// Move the element at `index` out of the raw buffer, ignoring the current length.
+    ///
+    /// # Safety
+    /// `index` must hold an initialized element that was hidden by
+    /// [`forget_all`](Self::forget_all) and has not been read yet.
+    #[inline(always)]
+    pub(crate) unsafe fn read_raw(&self, index: usize) -> T {
+        let base = match self {
+            Store::Inline(inline) => inline.buf.as_ptr().cast::<T>(),
+            Store::Heap(vec) => vec.as_ptr(),
+        };
+        // SAFETY: upheld by the caller; `index` lies within the buffer's capacity.
+        unsafe { ptr::read(base.add(index)) }
+    }
+
+    /// Move a heap store back inline when the elements fit.
+    #[inline]
+    pub(crate) fn shrink_to_fit(&mut self) {
+        if let Store::Heap(vec) = self {
+            if vec.len() <= N {
+                let mut inline = InlineVec::new();
+                let len = vec.len();
+                // SAFETY: `len <= N`, and the elements are moved bitwise out of the Vec,
+                // whose length is reset before it is dropped.
+                unsafe {
+                    ptr::copy_nonoverlapping(vec.as_ptr(), inline.buf.as_mut_ptr().cast::<T>(), len);
+                    vec.set_len(0);
+                }
+                inline.len = len as u16;
+                *self = Store::Inline(inline);
+            } else {
+                vec.shrink_to_fit();
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_slice(&self) -> &[T] {
+        match self {
+            Store::Inline(inline) => inline.as_slice(),
+            Store::Heap(vec) => vec.as_slice(),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        match self {
+            Store::Inline(inline) => inline.as_mut_slice(),
+            Store::Heap(vec) => vec.as_mut_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for Store<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for Store<T, N> {
+    fn clone(&self) -> Self {
+        match self {
+            Store::Inline(inline) => Store::Inline(inline.clone()),
+            Store::Heap(vec) => Store::Heap(vec.clone()),
+        }
+    }
+}
+
+impl<T, const N: usize> Deref for Store<T, N> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for Store<T, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
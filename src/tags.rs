@@ -0,0 +1,170 @@
+//! One-byte hash tags for the pre-spill scan (`fingerprint` feature).
+//!
+//! Each inline slot gets a one-byte tag derived from its key's hash, kept in a side
+//! array parallel to the inline store. A lookup compares the probe's tag against a whole
+//! group of tags at once and only compares full keys on a tag match, so an `N = 32` scan
+//! over string keys does about one `K == K` comparison instead of up to 32.
+//!
+//! The group width is picked at compile time: AVX2 (32 tags), SSE2 or NEON (16 tags),
+//! or a portable SWAR fallback (8 tags per `u64`). Leftover tags past the last full
+//! group are compared one by one.
+
+/// Tag for a key hash: the top byte after a Fibonacci multiply.
+///
+/// The hasher is pluggable, so the hash bits cannot be trusted to be mixed; an identity
+/// hasher on small integers leaves the top byte zero. The multiply folds every input bit
+/// into the top byte, and consecutive hashes get well-spread tags.
+#[inline(always)]
+pub(crate) fn tag(hash: u64) -> u8 {
+    (hash.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 56) as u8
+}
+
+/// Return the first slot whose tag equals `tag` and whose key `eq` confirms.
+#[inline(always)]
+pub(crate) fn find(tags: &[u8], tag: u8, mut eq: impl FnMut(usize) -> bool) -> Option<usize> {
+    let mut base = 0;
+    while base + imp::GROUP <= tags.len() {
+        // SAFETY: `base + GROUP <= tags.len()`, so the whole group is in bounds.
+        let mask = unsafe { imp::group_match(tags.as_ptr().add(base), tag) };
+        for lane in BitMask::<{ imp::STRIDE }>(mask) {
+            if eq(base + lane) {
+                return Some(base + lane);
+            }
+        }
+        base += imp::GROUP;
+    }
+    (base..tags.len()).find(|&i| tags[i] == tag && eq(i))
+}
+
+/// Match mask with one set bit per matching lane, each lane `STRIDE` bits wide.
+struct BitMask<const STRIDE: u32>(u64);
+
+impl<const STRIDE: u32> Iterator for BitMask<STRIDE> {
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let lane = self.0.trailing_zeros() / STRIDE;
+        self.0 &= self.0 - 1;
+        Some(lane as usize)
+    }
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "avx2"))]
+use avx2 as imp;
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(target_feature = "avx2")
+))]
+use sse2 as imp;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use neon as imp;
+#[cfg(not(any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+use portable as imp;
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "avx2"))]
+mod avx2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    pub(super) const GROUP: usize = 32;
+    pub(super) const STRIDE: u32 = 1;
+
+    /// # Safety
+    /// `ptr` must be valid for reading `GROUP` bytes.
+    #[inline(always)]
+    pub(super) unsafe fn group_match(ptr: *const u8, tag: u8) -> u64 {
+        // SAFETY: upheld by the caller; the load is unaligned.
+        unsafe {
+            let group = _mm256_loadu_si256(ptr.cast());
+            let eq = _mm256_cmpeq_epi8(group, _mm256_set1_epi8(tag as i8));
+            _mm256_movemask_epi8(eq) as u32 as u64
+        }
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(target_feature = "avx2")
+))]
+mod sse2 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    pub(super) const GROUP: usize = 16;
+    pub(super) const STRIDE: u32 = 1;
+
+    /// # Safety
+    /// `ptr` must be valid for reading `GROUP` bytes.
+    #[inline(always)]
+    pub(super) unsafe fn group_match(ptr: *const u8, tag: u8) -> u64 {
+        // SAFETY: upheld by the caller; the load is unaligned.
+        unsafe {
+            let group = _mm_loadu_si128(ptr.cast());
+            let eq = _mm_cmpeq_epi8(group, _mm_set1_epi8(tag as i8));
+            _mm_movemask_epi8(eq) as u16 as u64
+        }
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon {
+    use core::arch::aarch64::*;
+
+    pub(super) const GROUP: usize = 16;
+    pub(super) const STRIDE: u32 = 4;
+
+    /// # Safety
+    /// `ptr` must be valid for reading `GROUP` bytes.
+    #[inline(always)]
+    pub(super) unsafe fn group_match(ptr: *const u8, tag: u8) -> u64 {
+        // SAFETY: upheld by the caller.
+        unsafe {
+            let eq = vceqq_u8(vld1q_u8(ptr), vdupq_n_u8(tag));
+            // Narrow each 0xFF/0x00 byte to a nibble, then keep one bit per lane.
+            let nibbles = vshrn_n_u16::<4>(vreinterpretq_u16_u8(eq));
+            vget_lane_u64::<0>(vreinterpret_u64_u8(nibbles)) & 0x8888_8888_8888_8888
+        }
+    }
+}
+
+// Used as `imp` on targets without a SIMD path, and checked against the SIMD paths in tests.
+#[allow(dead_code)]
+pub(crate) mod portable {
+    pub(crate) const GROUP: usize = 8;
+    pub(crate) const STRIDE: u32 = 8;
+
+    const LO: u64 = 0x0101_0101_0101_0101;
+    const HI7: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+
+    /// # Safety
+    /// `ptr` must be valid for reading `GROUP` bytes.
+    #[inline(always)]
+    pub(crate) unsafe fn group_match(ptr: *const u8, tag: u8) -> u64 {
+        // SAFETY: upheld by the caller; the read is unaligned.
+        let word = u64::from_le(unsafe { ptr.cast::<u64>().read_unaligned() });
+        // Zero-byte detection without carries across lanes, so there are no false positives.
+        let x = word ^ (LO * tag as u64);
+        !(((x & HI7) + HI7) | x | HI7)
+    }
+
+    /// Lanes matching `tag` in `tags[..GROUP]`, for comparing against the SIMD paths.
+    #[cfg(test)]
+    pub(crate) fn lanes(tags: &[u8; GROUP], tag: u8) -> alloc::vec::Vec<usize> {
+        // SAFETY: `tags` is exactly one group.
+        let mask = unsafe { group_match(tags.as_ptr(), tag) };
+        super::BitMask::<STRIDE>(mask).collect()
+    }
+}
@@ -11,42 +11,44 @@ fn create_test_cache() -> TinyLru<&'static str, i32, 4> {
 }
 
 // Helper function to verify DLL structure
-fn verify_dll_structure<K: Eq + Hash + Default + Clone, V: Default, const N: usize>(cache: &TinyLru<K, V, N>) {
+fn verify_dll_structure<K: Eq + Hash, V, const N: usize, S: BuildHasher, I: LinkIndex, L: EvictionListener<K, V>>(
+    cache: &TinyLru<K, V, N, S, I, L>,
+) {
     if cache.is_empty() {
-        assert_eq!(cache.head, u16::MAX);
-        assert_eq!(cache.tail, u16::MAX);
+        assert_eq!(cache.head, I::NONE);
+        assert_eq!(cache.tail, I::NONE);
         return;
     }
 
     // Verify head and tail are valid
-    assert!(cache.head < cache.len());
-    assert!(cache.tail < cache.len());
+    assert!(cache.head.to_usize() < cache.len());
+    assert!(cache.tail.to_usize() < cache.len());
     
     // Verify head has no previous element
-    assert_eq!(cache.store[cache.head as usize].prev, u16::MAX);
+    assert_eq!(cache.store.prev(cache.head.to_usize()), I::NONE);
     
     // Verify tail has no next element
-    assert_eq!(cache.store[cache.tail as usize].next, u16::MAX);
+    assert_eq!(cache.store.next(cache.tail.to_usize()), I::NONE);
     
     // Verify DLL chain integrity
     let mut current = cache.head;
     let mut count = 0;
     
-    while current != u16::MAX {
+    while current != I::NONE {
         count += 1;
-        let entry = &cache.store[current as usize];
+        let (next, prev) = (cache.store.next(current.to_usize()), cache.store.prev(current.to_usize()));
         
         // Verify next->prev points back to current
-        if entry.next != u16::MAX {
-            assert_eq!(cache.store[entry.next as usize].prev, current);
+        if next != I::NONE {
+            assert_eq!(cache.store.prev(next.to_usize()), current);
         }
         
         // Verify prev->next points forward to current
-        if entry.prev != u16::MAX {
-            assert_eq!(cache.store[entry.prev as usize].next, current);
+        if prev != I::NONE {
+            assert_eq!(cache.store.next(prev.to_usize()), current);
         }
         
-        current = entry.next;
+        current = next;
     }
     
     // Verify we visited all elements
@@ -59,7 +61,7 @@ fn test_new() {
     assert_eq!(cache.len(), 0);
     assert!(cache.is_empty());
     assert_eq!(cache.capacity(), 4);
-    assert!(!cache.index.is_some());
+    assert!(cache.index.is_none());
     verify_dll_structure(&cache);
 }
 
@@ -69,7 +71,7 @@ fn test_with_capacity() {
     assert_eq!(cache.len(), 0);
     assert!(cache.is_empty());
     assert_eq!(cache.capacity(), 8);
-    assert!(!cache.index.is_some());
+    assert!(cache.index.is_none());
     verify_dll_structure(&cache);
 }
 
@@ -87,7 +89,7 @@ fn test_push_single() {
     assert_eq!(cache.len(), 1);
     assert!(!cache.is_empty());
     assert_eq!(cache.capacity(), 4);
-    assert!(!cache.index.is_some());
+    assert!(cache.index.is_none());
     verify_dll_structure(&cache);
 }
 
@@ -97,7 +99,7 @@ fn test_push_multiple() {
     
     assert_eq!(cache.len(), 3);
     assert!(!cache.is_empty());
-    assert!(!cache.index.is_some());
+    assert!(cache.index.is_none());
     verify_dll_structure(&cache);
 }
 
@@ -274,7 +276,7 @@ fn test_clear() {
     assert_eq!(cache.len(), 0);
     assert!(cache.is_empty());
     assert_eq!(cache.capacity(), 4); // Capacity should remain unchanged
-    assert!(!cache.index.is_some());
+    assert!(cache.index.is_none());
     
     verify_dll_structure(&cache);
 }
@@ -613,8 +615,8 @@ fn force_spill(cache: &mut TinyLru<&str, i32, 3>) {
 }
 
 // Helper function to verify index consistency post-spill
-fn verify_index_consistency<K: Eq + Hash + Default + Clone, V: Default, const N: usize>(
-    cache: &TinyLru<K, V, N>
+fn verify_index_consistency<K: Eq + Hash, V, const N: usize, S: BuildHasher, I: LinkIndex, L: EvictionListener<K, V>>(
+    cache: &TinyLru<K, V, N, S, I, L>
 ) {
     if cache.index.is_none() {
         return; // Pre-spill, no index to verify
@@ -623,20 +625,20 @@ fn verify_index_consistency<K: Eq + Hash + Default + Clone, V: Default, const N:
     let index = cache.index.as_ref().unwrap();
     
     // Verify all entries in the array have corresponding index entries
-    for i in 0..cache.len() as usize {
-        let key = &cache.store[i].key;
-        assert_eq!(index.get(key), Some(&(i as u16)), 
+    for i in 0..cache.len() {
+        let key = cache.store.key(i);
+        assert_eq!(cache.find_key_index(key), Some(i), 
                    "Index entry for key at position {} should point to index {}", i, i);
     }
     
     // Verify index size matches array size
-    assert_eq!(index.len(), cache.len() as usize,
+    assert_eq!(index.len(), cache.len(),
                "Index size {} should match array size {}", index.len(), cache.len());
 }
 
 // Helper function to verify post-spill state
-fn verify_post_spill_state<K: Eq + Hash + Default + Clone, V: Default, const N: usize>(
-    cache: &TinyLru<K, V, N>
+fn verify_post_spill_state<K: Eq + Hash, V, const N: usize, S: BuildHasher, I: LinkIndex, L: EvictionListener<K, V>>(
+    cache: &TinyLru<K, V, N, S, I, L>
 ) {
     assert!(cache.index.is_some(), "Cache should be in post-spill state");
     verify_index_consistency(cache);
@@ -780,7 +782,7 @@ fn test_push_existing_key_post_spill() {
     assert_eq!(cache.get(&"b"), Some(&200));
     
     // Verify "b" is now MRU (last in DLL)
-    assert_eq!(cache.tail, cache.find_key_index(&"b").unwrap() as u16);
+    assert_eq!(cache.tail.to_usize(), cache.find_key_index(&"b").unwrap());
 }
 
 #[test]
@@ -853,7 +855,7 @@ fn test_get_existing_key_post_spill() {
     verify_post_spill_state(&cache);
     
     // Verify "b" is promoted to MRU
-    assert_eq!(cache.tail, cache.find_key_index(&"b").unwrap() as u16);
+    assert_eq!(cache.tail.to_usize(), cache.find_key_index(&"b").unwrap());
 }
 
 #[test]
@@ -890,7 +892,7 @@ fn test_get_mut_post_spill() {
     assert_eq!(cache.get(&"b"), Some(&200));
     
     // Verify "b" is promoted to MRU
-    assert_eq!(cache.tail, cache.find_key_index(&"b").unwrap() as u16);
+    assert_eq!(cache.tail.to_usize(), cache.find_key_index(&"b").unwrap());
 }
 
 #[test]
@@ -1520,7 +1522,7 @@ fn test_unspill_preserves_lru_order() {
     
     // Promote "b" to MRU before unspill
     cache.get(&"b");
-    assert_eq!(cache.tail, cache.find_key_index(&"b").unwrap() as u16);
+    assert_eq!(cache.tail.to_usize(), cache.find_key_index(&"b").unwrap());
     
     // Unspill
     assert!(cache.unspill());
@@ -1529,7 +1531,7 @@ fn test_unspill_preserves_lru_order() {
     // Verify LRU order is preserved
     // "c" should be LRU (head), "b" should be MRU (tail)
     assert_eq!(cache.head, cache.find_key_index(&"c").unwrap() as u16);
-    assert_eq!(cache.tail, cache.find_key_index(&"b").unwrap() as u16);
+    assert_eq!(cache.tail.to_usize(), cache.find_key_index(&"b").unwrap());
     
     // Verify pop still returns LRU
     let popped = cache.pop();
@@ -1768,3 +1770,3043 @@ fn test_unspill_preserves_index_consistency() {
     
     verify_dll_structure(&cache);
 }
+
+// ============================================================================
+// ITERATOR TESTS
+// ============================================================================
+
+#[test]
+fn test_iter_empty() {
+    let cache: TinyLru<&str, i32, 4> = TinyLru::new();
+    assert_eq!(cache.iter().len(), 0);
+    assert_eq!(cache.iter().next(), None);
+    assert_eq!(cache.iter().next_back(), None);
+}
+
+#[test]
+fn test_iter_lru_to_mru_order() {
+    let mut cache = create_test_cache();
+    cache.get(&"a"); // Order: b, c, a
+
+    let pairs: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(pairs, [("b", 2), ("c", 3), ("a", 1)]);
+
+    let rev: Vec<_> = cache.iter().rev().map(|(k, _)| *k).collect();
+    assert_eq!(rev, ["a", "c", "b"]);
+}
+
+#[test]
+fn test_iter_follows_links_after_swap_remove() {
+    let mut cache: TinyLru<&str, i32, 4> = TinyLru::new();
+    cache.push("a", 1);
+    cache.push("b", 2);
+    cache.push("c", 3);
+    cache.push("d", 4);
+    cache.get(&"b");
+    cache.remove(&"a"); // "d" is swapped into slot 0
+
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["c", "d", "b"]);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_iter_double_ended_meets_in_middle() {
+    let cache = create_test_cache();
+    let mut iter = cache.iter();
+
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some((&"a", &1)));
+    assert_eq!(iter.next_back(), Some((&"c", &3)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some((&"b", &2)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn test_iter_mut_updates_values_without_promotion() {
+    let mut cache = create_test_cache();
+
+    for (_, val) in cache.iter_mut() {
+        *val *= 10;
+    }
+    for val in cache.values_mut().rev() {
+        *val += 1;
+    }
+
+    assert_eq!(cache.values().copied().collect::<Vec<_>>(), [11, 21, 31]);
+    // Ordering untouched: "a" is still LRU
+    assert_eq!(cache.pop(), Some(("a", 11)));
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_iter_into_iterator_for_refs() {
+    let mut cache = create_test_cache();
+
+    let mut sum = 0;
+    for (_, val) in &cache {
+        sum += *val;
+    }
+    assert_eq!(sum, 6);
+
+    for (_, val) in &mut cache {
+        *val = 0;
+    }
+    assert!(cache.values().all(|v| *v == 0));
+}
+
+#[test]
+fn test_iter_post_spill() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(6);
+    force_spill(&mut cache);
+    cache.push("e", 5);
+    cache.get(&"a");
+    cache.remove(&"c");
+    verify_post_spill_state(&cache);
+
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["b", "d", "e", "a"]);
+    assert_eq!(cache.keys().rev().copied().collect::<Vec<_>>(), ["a", "e", "d", "b"]);
+    assert_eq!(cache.values().len(), cache.len());
+}
+
+#[test]
+fn test_iter_same_order_before_and_after_unspill() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(4);
+    force_spill(&mut cache);
+    cache.get(&"b");
+    cache.pop();
+
+    let before: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+    assert!(cache.unspill());
+    let after: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+
+    assert_eq!(before, after);
+    assert_eq!(after, [("c", 3), ("d", 4), ("b", 2)]);
+}
+
+// ============================================================================
+// OWNING ITERATOR / DRAIN TESTS
+// ============================================================================
+
+#[test]
+fn test_into_iter_lru_order() {
+    let mut cache = create_test_cache();
+    cache.get(&"a");
+
+    let pairs: Vec<_> = cache.into_iter().collect();
+    assert_eq!(pairs, [("b", 2), ("c", 3), ("a", 1)]);
+}
+
+#[test]
+fn test_into_iter_rev_and_partial_consumption() {
+    let mut cache: TinyLru<String, String, 2> = TinyLru::with_capacity(4);
+    for i in 0..4 {
+        cache.push(format!("k{}", i), format!("v{}", i));
+    }
+    assert!(cache.is_spilled());
+
+    let mut iter = cache.into_iter().rev();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some(("k3".to_string(), "v3".to_string())));
+    assert_eq!(iter.next_back(), Some(("k0".to_string(), "v0".to_string())));
+    // Remaining owned entries are dropped with the iterator
+}
+
+#[test]
+fn test_drain_empties_and_resets_to_pre_spill() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(6);
+    force_spill(&mut cache);
+    cache.get(&"b");
+    assert!(cache.is_spilled());
+
+    let drained: Vec<_> = cache.drain().collect();
+    assert_eq!(drained, [("a", 1), ("c", 3), ("d", 4), ("b", 2)]);
+
+    assert!(cache.is_empty());
+    assert!(cache.index.is_none());
+    assert_eq!(cache.capacity(), 6);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_drain_mru_order() {
+    let mut cache = create_test_cache();
+    let drained: Vec<_> = cache.drain_mru().map(|(k, _)| k).collect();
+    assert_eq!(drained, ["c", "b", "a"]);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_drain_dropped_early_clears_cache() {
+    let mut cache = create_test_cache();
+    {
+        let mut drain = cache.drain();
+        assert_eq!(drain.next(), Some(("a", 1)));
+    }
+    assert!(cache.is_empty());
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_cache_reusable_after_drain() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(4);
+    force_spill(&mut cache);
+    cache.drain().for_each(drop);
+
+    cache.push("x", 1);
+    cache.push("y", 2);
+    cache.push("z", 3);
+    assert!(!cache.is_spilled());
+    cache.push("w", 4); // Spills again
+    verify_post_spill_state(&cache);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["x", "y", "z", "w"]);
+}
+
+// ============================================================================
+// ENTRY API TESTS
+// ============================================================================
+
+#[test]
+fn test_entry_vacant_insert() {
+    let mut cache: TinyLru<&str, i32, 4> = TinyLru::new();
+
+    match cache.entry("a") {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), &"a");
+            *entry.insert(1) += 1;
+        }
+        Entry::Occupied(_) => panic!("expected vacant entry"),
+    }
+
+    assert_eq!(cache.peek(&"a"), Some(&2));
+    assert_eq!(cache.len(), 1);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_entry_occupied_promotes_to_mru() {
+    let mut cache = create_test_cache();
+
+    match cache.entry("a") {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.get(), &1);
+            assert_eq!(entry.insert(10), 1);
+        }
+        Entry::Vacant(_) => panic!("expected occupied entry"),
+    }
+
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["b", "c", "a"]);
+    assert_eq!(cache.peek(&"a"), Some(&10));
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_entry_or_insert_with_only_called_on_miss() {
+    let mut cache = create_test_cache();
+    let mut calls = 0;
+
+    cache.entry("a").or_insert_with(|| { calls += 1; 100 });
+    assert_eq!(calls, 0);
+    cache.entry("z").or_insert_with_key(|k| { calls += 1; k.len() as i32 });
+    assert_eq!(calls, 1);
+
+    assert_eq!(cache.peek(&"a"), Some(&1));
+    assert_eq!(cache.peek(&"z"), Some(&1));
+}
+
+#[test]
+fn test_entry_and_modify_or_default() {
+    let mut cache: TinyLru<&str, i32, 4> = TinyLru::new();
+
+    *cache.entry("n").and_modify(|v| *v += 1).or_default() += 5;
+    *cache.entry("n").and_modify(|v| *v += 1).or_default() += 5;
+
+    assert_eq!(cache.peek(&"n"), Some(&11));
+}
+
+#[test]
+fn test_entry_vacant_evicts_lru_when_full() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::new();
+    cache.push("a", 1);
+    cache.push("b", 2);
+    cache.push("c", 3);
+
+    let Entry::Vacant(entry) = cache.entry("d") else { panic!("expected vacant entry") };
+    let (val, evicted) = entry.insert_evict(4);
+    *val += 1;
+
+    assert_eq!(evicted, Some(("a", 1)));
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["b", "c", "d"]);
+    assert_eq!(cache.peek(&"d"), Some(&5));
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_entry_vacant_triggers_spill() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(4);
+    cache.push("a", 1);
+    cache.push("b", 2);
+    cache.push("c", 3);
+
+    let Entry::Vacant(entry) = cache.entry("d") else { panic!("expected vacant entry") };
+    let (_, evicted) = entry.insert_evict(4);
+
+    assert_eq!(evicted, None);
+    verify_post_spill_state(&cache);
+    assert_eq!(cache.len(), 4);
+
+    // Post-spill eviction through the entry path
+    assert_eq!(*cache.entry("e").or_insert(5), 5);
+    assert!(!cache.contains_key(&"a"));
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_entry_occupied_remove() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(6);
+    force_spill(&mut cache);
+
+    let Entry::Occupied(entry) = cache.entry("b") else { panic!("expected occupied entry") };
+    assert_eq!(entry.remove_entry(), ("b", 2));
+
+    assert!(!cache.contains_key(&"b"));
+    assert_eq!(cache.len(), 3);
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_entry_vacant_into_key_leaves_cache_untouched() {
+    let mut cache = create_test_cache();
+    let Entry::Vacant(entry) = cache.entry("z") else { panic!("expected vacant entry") };
+    assert_eq!(entry.into_key(), "z");
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["a", "b", "c"]);
+}
+
+// ============================================================================
+// PUSH_EVICT / TRY_PUSH TESTS
+// ============================================================================
+
+#[test]
+fn test_push_evict_returns_none_with_room() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::new();
+    assert_eq!(cache.push_evict("a", 1), None);
+    assert_eq!(cache.push_evict("b", 2), None);
+    assert_eq!(cache.push_evict("c", 3), None);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_push_evict_returns_old_value_on_update() {
+    let mut cache = create_test_cache();
+    assert_eq!(cache.push_evict("a", 10), Some(("a", 1)));
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["b", "c", "a"]);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_push_evict_returns_lru_when_full() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::new();
+    cache.push("a", 1);
+    cache.push("b", 2);
+    cache.push("c", 3);
+    cache.get(&"a");
+
+    assert_eq!(cache.push_evict("d", 4), Some(("b", 2)));
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["c", "a", "d"]);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_push_evict_spills_instead_of_evicting() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(4);
+    cache.push("a", 1);
+    cache.push("b", 2);
+    cache.push("c", 3);
+
+    assert_eq!(cache.push_evict("d", 4), None);
+    verify_post_spill_state(&cache);
+    assert_eq!(cache.push_evict("e", 5), Some(("a", 1)));
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_try_push_insert_and_update() {
+    let mut cache: TinyLru<&str, i32, 2> = TinyLru::new();
+    assert_eq!(cache.try_push("a", 1), Ok(None));
+    assert_eq!(cache.try_push("b", 2), Ok(None));
+    assert_eq!(cache.try_push("a", 3), Ok(Some(1)));
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["b", "a"]);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_try_push_refuses_when_full() {
+    let mut cache: TinyLru<String, i32, 2> = TinyLru::new();
+    cache.push("a".to_string(), 1);
+    cache.push("b".to_string(), 2);
+
+    assert_eq!(cache.try_push("c".to_string(), 3), Err(("c".to_string(), 3)));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.keys().cloned().collect::<Vec<_>>(), ["a", "b"]);
+}
+
+#[test]
+fn test_try_push_spills_when_capacity_allows() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(4);
+    cache.push("a", 1);
+    cache.push("b", 2);
+    cache.push("c", 3);
+
+    assert_eq!(cache.try_push("d", 4), Ok(None));
+    verify_post_spill_state(&cache);
+    assert_eq!(cache.try_push("e", 5), Err(("e", 5)));
+    verify_post_spill_state(&cache);
+}
+
+// ============================================================================
+// UNBOUNDED KEY/VALUE TYPE TESTS (no Default / Clone required)
+// ============================================================================
+
+// Key with neither `Default` nor `Clone`
+#[derive(PartialEq, Eq, Hash, Debug)]
+struct OpaqueKey(u32);
+
+// Value with neither `Default` nor `Clone` that records its drops
+struct DropTracker {
+    id: u32,
+    drops: std::rc::Rc<core::cell::RefCell<Vec<u32>>>,
+}
+
+impl Drop for DropTracker {
+    fn drop(&mut self) {
+        self.drops.borrow_mut().push(self.id);
+    }
+}
+
+fn tracker(id: u32, drops: &std::rc::Rc<core::cell::RefCell<Vec<u32>>>) -> DropTracker {
+    DropTracker { id, drops: drops.clone() }
+}
+
+// Snapshot of the ids dropped so far, in drop order
+fn dropped(drops: &std::rc::Rc<core::cell::RefCell<Vec<u32>>>) -> Vec<u32> {
+    core::cell::RefCell::borrow(drops).clone()
+}
+
+#[test]
+fn test_opaque_key_and_value_pre_and_post_spill() {
+    let drops = std::rc::Rc::new(core::cell::RefCell::new(Vec::new()));
+    let mut cache: TinyLru<OpaqueKey, DropTracker, 2> = TinyLru::with_capacity(3);
+
+    cache.push(OpaqueKey(1), tracker(1, &drops));
+    cache.push(OpaqueKey(2), tracker(2, &drops));
+    assert!(!cache.is_spilled());
+    cache.push(OpaqueKey(3), tracker(3, &drops));
+    assert!(cache.is_spilled());
+    verify_post_spill_state(&cache);
+
+    assert_eq!(cache.get(&OpaqueKey(1)).map(|v| v.id), Some(1));
+    let (key, _) = cache.remove(&OpaqueKey(2)).unwrap();
+    assert_eq!(key, OpaqueKey(2));
+    verify_post_spill_state(&cache);
+
+    cache.pop();
+    assert!(cache.unspill());
+    assert_eq!(cache.peek(&OpaqueKey(1)).map(|v| v.id), Some(1));
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_every_value_dropped_exactly_once() {
+    let drops = std::rc::Rc::new(core::cell::RefCell::new(Vec::new()));
+    {
+        let mut cache: TinyLru<u32, DropTracker, 2> = TinyLru::with_capacity(3);
+        for i in 0..3 {
+            cache.push(i, tracker(i, &drops));
+        }
+        cache.push(3, tracker(3, &drops)); // Evicts 0
+        cache.push(1, tracker(10, &drops)); // Replaces value 1
+        assert_eq!(dropped(&drops), [0, 1]);
+
+        drop(cache.remove(&2));
+        assert_eq!(dropped(&drops), [0, 1, 2]);
+    }
+    // Remaining values (3 and 10) dropped with the cache
+    let mut all = dropped(&drops);
+    all.sort();
+    assert_eq!(all, [0, 1, 2, 3, 10]);
+}
+
+#[test]
+fn test_partial_into_iter_and_drain_drop_remaining_once() {
+    let drops = std::rc::Rc::new(core::cell::RefCell::new(Vec::new()));
+
+    let mut cache: TinyLru<u32, DropTracker, 4> = TinyLru::new();
+    for i in 0..4 {
+        cache.push(i, tracker(i, &drops));
+    }
+    let mut iter = cache.into_iter();
+    drop(iter.next());
+    drop(iter.next_back());
+    assert_eq!(dropped(&drops), [0, 3]);
+    drop(iter);
+    assert_eq!(dropped(&drops), [0, 3, 1, 2]);
+
+    drops.borrow_mut().clear();
+    let mut cache: TinyLru<u32, DropTracker, 2> = TinyLru::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, tracker(i, &drops));
+    }
+    drop(cache.drain().next());
+    assert_eq!(dropped(&drops), [0, 1, 2, 3]);
+    assert!(cache.is_empty());
+
+    drops.borrow_mut().clear();
+    cache.push(7, tracker(7, &drops));
+    cache.clear();
+    assert_eq!(dropped(&drops), [7]);
+}
+
+#[test]
+fn test_clone_inline_and_spilled() {
+    let mut cache: TinyLru<String, String, 2> = TinyLru::with_capacity(3);
+    cache.push("a".to_string(), "1".to_string());
+    cache.push("b".to_string(), "2".to_string());
+
+    let inline_copy = cache.clone();
+    cache.push("c".to_string(), "3".to_string());
+    let spilled_copy = cache.clone();
+    cache.clear();
+
+    assert_eq!(inline_copy.keys().cloned().collect::<Vec<_>>(), ["a", "b"]);
+    assert!(!inline_copy.is_spilled());
+    assert_eq!(spilled_copy.keys().cloned().collect::<Vec<_>>(), ["a", "b", "c"]);
+    verify_post_spill_state(&spilled_copy);
+}
+
+// ============================================================================
+// BORROWED LOOKUP TESTS
+// ============================================================================
+
+#[test]
+fn test_borrowed_str_lookup_pre_spill() {
+    let mut cache: TinyLru<String, i32, 4> = TinyLru::new();
+    cache.push("a".to_string(), 1);
+    cache.push("b".to_string(), 2);
+
+    assert!(cache.contains_key("a"));
+    assert_eq!(cache.peek("b"), Some(&2));
+    assert_eq!(cache.get("a"), Some(&1));
+    *cache.get_mut("b").unwrap() += 10;
+    assert_eq!(cache.keys().map(String::as_str).collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(cache.remove("a"), Some(("a".to_string(), 1)));
+    assert_eq!(cache.peek("b"), Some(&12));
+    assert!(!cache.contains_key("missing"));
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_borrowed_str_lookup_post_spill() {
+    let mut cache: TinyLru<String, i32, 2> = TinyLru::with_capacity(8);
+    for i in 0..6 {
+        cache.push(format!("key{}", i), i);
+    }
+    verify_post_spill_state(&cache);
+
+    assert!(cache.contains_key("key3"));
+    assert_eq!(cache.peek("key5"), Some(&5));
+    assert_eq!(cache.get("key0"), Some(&0));
+    *cache.get_mut("key1").unwrap() = 100;
+    assert_eq!(cache.remove("key2"), Some(("key2".to_string(), 2)));
+    assert_eq!(cache.get("key2"), None);
+    assert_eq!(cache.peek("key1"), Some(&100));
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_borrowed_slice_lookup_for_vec_keys() {
+    let mut cache: TinyLru<Vec<u8>, &str, 1> = TinyLru::with_capacity(2);
+    cache.push(vec![1, 2], "a");
+    cache.push(vec![3], "b"); // Spills
+
+    let probe: &[u8] = &[1, 2];
+    assert_eq!(cache.peek(probe), Some(&"a"));
+    assert_eq!(cache.remove(&[3][..]), Some((vec![3], "b")));
+    verify_post_spill_state(&cache);
+}
+
+// ============================================================================
+// PLUGGABLE HASHER TESTS
+// ============================================================================
+
+// Pathological hasher: every key collides, so the index must rely on key equality.
+#[derive(Clone, Default)]
+struct ConstantHasher;
+
+impl core::hash::Hasher for ConstantHasher {
+    fn finish(&self) -> u64 {
+        42
+    }
+    fn write(&mut self, _bytes: &[u8]) {}
+}
+
+impl core::hash::BuildHasher for ConstantHasher {
+    type Hasher = ConstantHasher;
+    fn build_hasher(&self) -> ConstantHasher {
+        ConstantHasher
+    }
+}
+
+// Identity hasher for pre-hashed `u64` keys.
+#[derive(Clone, Default)]
+struct IdentityHasher(u64);
+
+impl core::hash::Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, _bytes: &[u8]) {
+        unreachable!("IdentityHasher only supports u64 keys");
+    }
+    fn write_u64(&mut self, n: u64) {
+        self.0 = n;
+    }
+}
+
+type IdentityBuildHasher = core::hash::BuildHasherDefault<IdentityHasher>;
+
+#[test]
+fn test_with_hasher_random_state() {
+    let mut cache: TinyLru<String, i32, 2, std::collections::hash_map::RandomState> =
+        TinyLru::with_capacity_and_hasher(4, Default::default());
+    for i in 0..4 {
+        cache.push(format!("k{}", i), i);
+    }
+    verify_post_spill_state(&cache);
+    assert_eq!(cache.get("k2"), Some(&2));
+    cache.push("k4".to_string(), 4);
+    assert!(!cache.contains_key("k0"));
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_identity_hasher_for_prehashed_keys() {
+    let mut cache: TinyLru<u64, u64, 2, IdentityBuildHasher> =
+        TinyLru::with_capacity_and_hasher(16, IdentityBuildHasher::default());
+    for k in 0..16u64 {
+        cache.push(k << 32, k);
+    }
+    verify_post_spill_state(&cache);
+    for k in 0..16u64 {
+        assert_eq!(cache.peek(&(k << 32)), Some(&k));
+    }
+}
+
+#[test]
+fn test_fully_colliding_hasher_post_spill() {
+    let mut cache: TinyLru<u32, u32, 2, ConstantHasher> = TinyLru::with_capacity_and_hasher(8, ConstantHasher);
+    for i in 0..8 {
+        cache.push(i, i * 10);
+    }
+    verify_post_spill_state(&cache);
+
+    // Evictions, removals and swaps must pick the right slot even though all hashes collide
+    cache.push(8, 80);
+    cache.get(&3);
+    assert_eq!(cache.remove(&5), Some((5, 50)));
+    assert_eq!(cache.pop(), Some((1, 10)));
+    verify_post_spill_state(&cache);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), [2, 4, 6, 7, 8, 3]);
+    for k in [2, 3, 4, 6, 7, 8] {
+        assert_eq!(cache.peek(&k), Some(&(k * 10)));
+    }
+}
+
+#[test]
+fn test_default_with_custom_hasher() {
+    let cache: TinyLru<u32, u32, 4, ConstantHasher> = TinyLru::default();
+    assert_eq!(cache.capacity(), 4);
+    assert!(cache.is_empty());
+    let _: &ConstantHasher = cache.hasher();
+}
+
+// ============================================================================
+// LINK INDEX WIDTH TESTS
+// ============================================================================
+
+type U8Lru<const N: usize> = TinyLru<u32, u32, N, FxBuildHasher, u8>;
+type U32Lru<const N: usize> = TinyLru<u32, u32, N, FxBuildHasher, u32>;
+
+#[test]
+fn test_u8_links_full_range() {
+    let mut cache = U8Lru::<4>::with_capacity(u8::MAX_CAPACITY);
+    assert_eq!(cache.capacity(), 254);
+    for i in 0..254 {
+        cache.push(i, i);
+    }
+    verify_post_spill_state(&cache);
+
+    // At capacity: the next push evicts the LRU instead of overflowing the sentinel
+    cache.push(254, 254);
+    assert_eq!(cache.len(), 254);
+    assert!(!cache.contains_key(&0));
+    cache.get(&1);
+    assert_eq!(cache.pop(), Some((2, 2)));
+    assert_eq!(cache.remove(&100), Some((100, 100)));
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be <= I::MAX_CAPACITY")]
+fn test_u8_capacity_above_limit() {
+    let _cache = U8Lru::<4>::with_capacity(255);
+}
+
+#[test]
+#[should_panic(expected = "new_cap must be <= I::MAX_CAPACITY")]
+fn test_u8_set_capacity_above_limit() {
+    let mut cache = U8Lru::<4>::new();
+    cache.set_capacity(255);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be <= I::MAX_CAPACITY")]
+fn test_u16_capacity_above_limit() {
+    let _cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(65_535);
+}
+
+#[test]
+fn test_u32_links_beyond_u16_range() {
+    const COUNT: u32 = 70_000;
+    let mut cache = U32Lru::<8>::with_capacity(COUNT as usize);
+    for i in 0..COUNT {
+        cache.push(i, i * 2);
+    }
+    assert_eq!(cache.len(), COUNT as usize);
+    verify_post_spill_state(&cache);
+
+    // Slots past u16::MAX must stay addressable through links and the index
+    assert_eq!(cache.get(&0), Some(&0));
+    assert_eq!(cache.peek(&(COUNT - 1)), Some(&((COUNT - 1) * 2)));
+    cache.push(COUNT, 0);
+    assert!(!cache.contains_key(&1));
+    assert_eq!(cache.remove(&66_000), Some((66_000, 132_000)));
+    verify_post_spill_state(&cache);
+
+    assert_eq!(cache.iter().next(), Some((&2, &4)));
+    assert_eq!(cache.iter().next_back(), Some((&COUNT, &0)));
+    assert_eq!(cache.drain().count(), COUNT as usize - 1);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_u8_node_is_smaller() {
+    assert!(core::mem::size_of::<Node<u8, u8, u8>>() < core::mem::size_of::<Node<u8, u8>>());
+    assert!(core::mem::size_of::<Node<u8, u8, u32>>() > core::mem::size_of::<Node<u8, u8>>());
+}
+
+// ============================================================================
+// RESIZE TESTS
+// ============================================================================
+
+#[test]
+fn test_resize_evicts_lru_in_order() {
+    let mut cache: TinyLru<&str, i32, 2> = TinyLru::with_capacity(6);
+    for (i, k) in ["a", "b", "c", "d", "e", "f"].into_iter().enumerate() {
+        cache.push(k, i as i32);
+    }
+    cache.get(&"a"); // a becomes MRU
+
+    assert_eq!(cache.resize(3), vec![("b", 1), ("c", 2), ("d", 3)]);
+    assert_eq!(cache.capacity(), 3);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["e", "f", "a"]);
+    // Still above N, so the index stays
+    verify_post_spill_state(&cache);
+
+    // The new bound is enforced by later pushes
+    cache.push("g", 6);
+    assert_eq!(cache.len(), 3);
+    assert!(!cache.contains_key(&"e"));
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_resize_unspills_when_fitting_inline() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(8);
+    force_spill(&mut cache);
+    cache.push("e", 5);
+    assert!(cache.is_spilled());
+
+    assert_eq!(cache.resize(3), vec![("a", 1), ("b", 2)]);
+    assert!(!cache.is_spilled());
+    assert!(cache.index.is_none());
+    verify_dll_structure(&cache);
+    assert_eq!(cache.get(&"c"), Some(&3));
+
+    // Evicts from inline storage without spilling again
+    cache.push("f", 6);
+    assert!(!cache.is_spilled());
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["e", "c", "f"]);
+}
+
+#[test]
+fn test_resize_grow_and_noop() {
+    let mut cache = create_test_cache();
+    assert!(cache.resize(4).is_empty());
+    assert!(cache.resize(10).is_empty());
+    assert_eq!(cache.capacity(), 10);
+    assert_eq!(cache.len(), 3);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_resize_evict_callback() {
+    let mut cache: TinyLru<String, i32, 2> = TinyLru::with_capacity(5);
+    for i in 0..5 {
+        cache.push(format!("k{}", i), i);
+    }
+
+    let mut seen = Vec::new();
+    cache.resize_evict(2, |k, v| seen.push((k, v)));
+    assert_eq!(seen, [("k0".to_string(), 0), ("k1".to_string(), 1), ("k2".to_string(), 2)]);
+    assert!(!cache.is_spilled());
+    assert_eq!(cache.peek("k4"), Some(&4));
+    verify_dll_structure(&cache);
+}
+
+#[test]
+#[should_panic(expected = "new_cap must be >= N")]
+fn test_resize_below_n() {
+    let mut cache = create_test_cache();
+    cache.resize(3);
+}
+
+// ============================================================================
+// UNSPILL POLICY TESTS
+// ============================================================================
+
+#[test]
+fn test_unspill_policy_never_is_default() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(8);
+    assert_eq!(cache.unspill_policy(), UnspillPolicy::Never);
+    force_spill(&mut cache);
+    cache.pop();
+    cache.pop();
+    cache.remove(&"c");
+    assert!(cache.is_spilled());
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_unspill_policy_eager() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(8);
+    cache.set_unspill_policy(UnspillPolicy::Eager);
+    force_spill(&mut cache);
+    cache.push("e", 5);
+
+    cache.remove(&"c");
+    assert!(cache.is_spilled()); // 4 entries left
+    cache.remove(&"d");
+    assert!(!cache.is_spilled());
+    assert!(cache.index.is_none());
+    verify_dll_structure(&cache);
+    assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["a", "b", "e"]);
+
+    // Eviction on a full cache never unspills mid-push
+    let mut full: TinyLru<&str, i32, 3> = TinyLru::with_capacity(4);
+    full.set_unspill_policy(UnspillPolicy::Eager);
+    force_spill(&mut full);
+    full.push("e", 5);
+    assert!(full.is_spilled());
+    verify_post_spill_state(&full);
+}
+
+#[test]
+fn test_unspill_policy_through_entry_remove() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(8);
+    cache.set_unspill_policy(UnspillPolicy::Eager);
+    force_spill(&mut cache);
+    if let Entry::Occupied(entry) = cache.entry("b") {
+        assert_eq!(entry.remove(), 2);
+    }
+    assert!(!cache.is_spilled());
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_unspill_policy_hysteresis() {
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(32);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 10 });
+    for i in 0..6 {
+        cache.push(i, i);
+    }
+
+    // Fits in N but stays spilled until the low watermark
+    while cache.len() > 3 {
+        cache.pop();
+    }
+    assert!(cache.is_spilled());
+    cache.pop();
+    assert!(!cache.is_spilled());
+    verify_dll_structure(&cache);
+
+    // Oscillating across N re-spills once, then stays spilled
+    for round in 0..3 {
+        for i in 0..4 {
+            cache.push(100 + round * 10 + i, i);
+        }
+        assert!(cache.is_spilled());
+        while cache.len() > 1 {
+            cache.pop();
+        }
+        assert!(cache.is_spilled());
+        verify_post_spill_state(&cache);
+    }
+
+    // Growing to the high watermark re-arms it
+    for i in 0..9 {
+        cache.push(1000 + i, i);
+    }
+    assert_eq!(cache.len(), 10);
+    while cache.len() > 2 {
+        cache.pop();
+    }
+    assert!(!cache.is_spilled());
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_set_unspill_policy_applies_immediately() {
+    let mut cache: TinyLru<&str, i32, 3> = TinyLru::with_capacity(8);
+    force_spill(&mut cache);
+    cache.pop();
+    assert!(cache.is_spilled());
+    cache.set_unspill_policy(UnspillPolicy::Eager);
+    assert!(!cache.is_spilled());
+}
+
+#[test]
+#[should_panic(expected = "low watermark must be <= N")]
+fn test_unspill_policy_low_above_n() {
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(32);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 5, high: 10 });
+}
+
+#[test]
+#[should_panic(expected = "high watermark must be > N")]
+fn test_unspill_policy_high_not_above_n() {
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(32);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 4 });
+}
+
+#[test]
+#[should_panic(expected = "high watermark must be <= capacity")]
+fn test_unspill_policy_high_above_capacity() {
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(8);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 9 });
+}
+
+#[test]
+fn test_unspill_policy_high_at_capacity_rearms() {
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(8);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 8 });
+    for round in 0..3 {
+        // Filling to capacity reaches `high`, so every round unspills again
+        for i in 0..8 {
+            cache.push(round * 10 + i, i);
+        }
+        assert!(cache.is_spilled());
+        while cache.len() > 2 {
+            cache.pop();
+        }
+        assert!(!cache.is_spilled());
+        verify_dll_structure(&cache);
+    }
+}
+
+#[test]
+fn test_set_capacity_below_high_watermark_clamps() {
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(16);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 10 });
+    cache.set_capacity(8);
+    assert_eq!(cache.unspill_policy(), UnspillPolicy::Hysteresis { low: 2, high: 8 });
+}
+
+#[test]
+fn test_resize_under_hysteresis() {
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(16);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 12 });
+    for i in 0..16 {
+        cache.push(i, i);
+    }
+
+    // Shrinking while spilled keeps the policy usable with the new bound
+    assert_eq!(cache.resize(6).len(), 10);
+    assert_eq!(cache.unspill_policy(), UnspillPolicy::Hysteresis { low: 2, high: 6 });
+    assert!(cache.is_spilled());
+    while cache.len() > 2 {
+        cache.pop();
+    }
+    assert!(!cache.is_spilled());
+
+    // Refilling to the clamped watermark re-arms it
+    for round in 0..2 {
+        for i in 0..6 {
+            cache.push(100 + round * 10 + i, i);
+        }
+        assert!(cache.is_spilled());
+        while cache.len() > 2 {
+            cache.pop();
+        }
+        assert!(!cache.is_spilled());
+        verify_dll_structure(&cache);
+    }
+
+    // Shrinking while disarmed, to a bound the cache already fills, re-arms at once
+    let mut cache: TinyLru<u32, u32, 4> = TinyLru::with_capacity(16);
+    cache.set_unspill_policy(UnspillPolicy::Hysteresis { low: 2, high: 12 });
+    for i in 0..10 {
+        cache.push(i, i);
+    }
+    while cache.len() > 2 {
+        cache.pop();
+    }
+    for i in 0..3 {
+        cache.push(100 + i, i);
+    }
+    assert!(cache.is_spilled());
+    cache.resize_evict(5, |_, _| {});
+    while cache.len() > 2 {
+        cache.pop();
+    }
+    assert!(!cache.is_spilled());
+}
+
+// ============================================================================
+// FINGERPRINT TAG TESTS
+// ============================================================================
+
+// Small deterministic generator so the tag scans see varied, repeatable data
+#[cfg(feature = "fingerprint")]
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[cfg(feature = "fingerprint")]
+#[test]
+fn test_tag_find_matches_scalar_scan() {
+    let mut state = 0x9e37_79b9_7f4a_7c15;
+    for len in 0..=80 {
+        // Few distinct tags, so groups contain several matches
+        let tags: Vec<u8> = (0..len).map(|_| (xorshift(&mut state) % 4) as u8).collect();
+        for tag in 0..5u8 {
+            let expected: Vec<usize> = (0..len).filter(|&i| tags[i] == tag).collect();
+            let mut seen = Vec::new();
+            assert_eq!(tags::find(&tags, tag, |i| { seen.push(i); false }), None);
+            assert_eq!(seen, expected, "len {} tag {}", len, tag);
+
+            // Stops at the first confirmed match
+            for &want in &expected {
+                assert_eq!(tags::find(&tags, tag, |i| i == want), Some(want));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fingerprint")]
+#[test]
+fn test_tag_portable_group_matches_scalar() {
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..1000 {
+        let mut group = [0u8; tags::portable::GROUP];
+        for b in &mut group {
+            // Include 0x00, 0x80 and 0xFF, the usual SWAR edge cases
+            *b = [0x00, 0x01, 0x7f, 0x80, 0xff][(xorshift(&mut state) % 5) as usize];
+        }
+        for tag in [0x00, 0x01, 0x7f, 0x80, 0xff] {
+            let expected: Vec<usize> = (0..group.len()).filter(|&i| group[i] == tag).collect();
+            assert_eq!(tags::portable::lanes(&group, tag), expected);
+        }
+    }
+}
+
+#[cfg(feature = "fingerprint")]
+#[test]
+fn test_tag_spreads_unmixed_hashes() {
+    // An identity hasher on small keys only sets the low bits, or only the high bits
+    // once shifted; both must still give mostly distinct tags
+    let low: std::collections::HashSet<u8> = (0..64u64).map(tags::tag).collect();
+    assert_eq!(low.len(), 64);
+    let high: std::collections::HashSet<u8> = (0..64u64).map(|i| tags::tag(i << 40)).collect();
+    assert!(high.len() >= 48, "{} distinct tags", high.len());
+}
+
+#[test]
+fn test_string_keys_full_inline_scan() {
+    let mut cache: TinyLru<String, usize, 64> = TinyLru::new();
+    for i in 0..64 {
+        cache.push(format!("key-{}", i), i);
+    }
+    assert!(!cache.is_spilled());
+    for i in 0..64 {
+        assert_eq!(cache.peek(format!("key-{}", i).as_str()), Some(&i));
+    }
+    assert!(!cache.contains_key("key-64"));
+
+    // Swap-removes from the middle keep every slot findable
+    for i in (0..64).step_by(3) {
+        assert_eq!(cache.remove(format!("key-{}", i).as_str()), Some((format!("key-{}", i), i)));
+    }
+    cache.pop();
+    for i in 2..64 {
+        assert_eq!(cache.contains_key(format!("key-{}", i).as_str()), i % 3 != 0, "key-{}", i);
+    }
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_inline_lookup_after_unspill() {
+    let mut cache: TinyLru<String, usize, 16> = TinyLru::with_capacity(64);
+    for i in 0..40 {
+        cache.push(format!("k{}", i), i);
+    }
+    // Reorder before shrinking, so inline slots no longer match insertion order
+    for i in (0..40).rev() {
+        cache.get(format!("k{}", i).as_str());
+    }
+    assert_eq!(cache.resize(16).len(), 24);
+    assert!(!cache.is_spilled());
+    for i in 0..16 {
+        assert_eq!(cache.peek(format!("k{}", i).as_str()), Some(&i));
+    }
+    cache.push("new".to_string(), 99);
+    assert_eq!(cache.get("new"), Some(&99));
+    assert!(!cache.contains_key("k15"));
+}
+
+#[test]
+fn test_inline_lookup_with_colliding_hasher() {
+    // Every tag is equal, so each lookup falls back to comparing all keys
+    let mut cache: TinyLru<u32, u32, 32, ConstantHasher> = TinyLru::with_hasher(ConstantHasher);
+    for i in 0..32 {
+        cache.push(i, i + 1);
+    }
+    cache.remove(&7);
+    for i in 0..32 {
+        assert_eq!(cache.peek(&i).copied(), if i == 7 { None } else { Some(i + 1) });
+    }
+}
+
+// ============================================================================
+// LARGE VALUE TESTS
+// ============================================================================
+
+#[test]
+fn test_large_values_through_spill_and_unspill() {
+    let mut cache: TinyLru<u32, [u64; 32], 4> = TinyLru::with_capacity(8);
+    for i in 0..8 {
+        cache.push(i, [i as u64; 32]);
+    }
+    assert!(cache.is_spilled());
+    cache.get(&0);
+    for (_, v) in cache.iter_mut() {
+        v[31] += 1;
+    }
+    assert_eq!(cache.remove(&3).map(|(_, v)| v[31]), Some(4));
+    assert_eq!(cache.resize(4).len(), 3);
+    assert!(!cache.is_spilled());
+    verify_dll_structure(&cache);
+
+    let drained: Vec<_> = cache.drain().map(|(k, v)| (k, v[0], v[31])).collect();
+    assert_eq!(drained, [(5, 5, 6), (6, 6, 7), (7, 7, 8), (0, 0, 1)]);
+}
+
+// ============================================================================
+// HASH-ONLY INDEX TESTS
+// ============================================================================
+
+thread_local! {
+    static KEY_CLONES: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+// `Clone` is implemented only to count calls: the index must never use it.
+#[derive(PartialEq, Eq, Hash, Debug)]
+struct CountedKey(String);
+
+impl Clone for CountedKey {
+    fn clone(&self) -> Self {
+        KEY_CLONES.with(|c| c.set(c.get() + 1));
+        CountedKey(self.0.clone())
+    }
+}
+
+#[test]
+fn test_post_spill_index_never_clones_keys() {
+    let mut cache: TinyLru<CountedKey, u32, 4> = TinyLru::with_capacity(16);
+    for i in 0..64 {
+        cache.push(CountedKey(format!("key-{}", i)), i);
+        cache.get(&CountedKey(format!("key-{}", i / 2)));
+        if i % 5 == 4 {
+            cache.remove(&CountedKey(format!("key-{}", i - 1)));
+        }
+    }
+    cache.pop();
+    verify_post_spill_state(&cache);
+    cache.resize(4);
+    cache.clear();
+    assert_eq!(KEY_CLONES.with(|c| c.get()), 0);
+}
+
+#[test]
+fn test_post_spill_index_holds_slot_ids_only() {
+    let mut cache: TinyLru<String, u32, 2> = TinyLru::with_capacity(8);
+    for i in 0..8 {
+        cache.push(format!("k{}", i), i);
+    }
+    cache.remove("k3");
+    cache.push("k8".to_string(), 8); // Evicts k0
+
+    // One `I`-sized record per entry, no key copies
+    let index: &HashTable<u16> = cache.index.as_ref().unwrap();
+    assert_eq!(index.len(), cache.len());
+
+    // Each slot id appears exactly once
+    let mut slots: Vec<usize> = index.iter().map(|&slot| slot as usize).collect();
+    slots.sort_unstable();
+    assert_eq!(slots, (0..cache.len()).collect::<Vec<_>>());
+
+    // Each record is filed under its own key's hash, and that key belongs to the slot's value
+    for &slot in &slots {
+        let key = cache.store.key(slot);
+        assert_eq!(*key, format!("k{}", cache.store.val(slot)));
+        let hash = cache.hasher().hash_one(key);
+        assert_eq!(index.find(hash, |&i| i as usize == slot), Some(&(slot as u16)));
+    }
+}
+
+// ============================================================================
+// STATS TESTS
+// ============================================================================
+
+#[test]
+#[cfg(not(feature = "stats"))]
+fn test_counters_are_zero_sized_without_stats() {
+    assert_eq!(core::mem::size_of::<stats::Counters>(), 0);
+}
+
+#[test]
+#[cfg(feature = "stats")]
+fn test_stats_count_lookups_and_writes() {
+    let mut cache = TinyLru::<i32, i32, 4>::new();
+    assert_eq!(cache.stats(), CacheStats::default());
+
+    cache.push(1, 10);
+    cache.push(2, 20);
+    cache.push(1, 11);
+    assert_eq!(cache.try_push(2, 21), Ok(Some(20)));
+    assert_eq!(cache.try_push(3, 30), Ok(None));
+
+    cache.get(&1);
+    cache.get(&9);
+    cache.get_mut(&2);
+    cache.peek(&3);
+    cache.peek(&8);
+    *cache.entry(4).or_insert(0) += 1;
+    *cache.entry(4).or_insert(0) += 1;
+    assert!(!cache.contains_key(&7));
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits, 4);
+    assert_eq!(stats.misses, 3);
+    assert_eq!(stats.lookups(), 7);
+    assert_eq!(stats.inserts, 4);
+    assert_eq!(stats.updates, 2);
+    assert_eq!(stats.evictions, 0);
+    assert_eq!((stats.spills, stats.unspills), (0, 0));
+}
+
+#[test]
+#[cfg(feature = "stats")]
+fn test_stats_count_evictions_not_removals() {
+    let mut cache = TinyLru::<i32, i32, 2>::new();
+    for i in 0..5 {
+        cache.push(i, i);
+    }
+    assert_eq!(cache.stats().evictions, 3);
+
+    // Explicit removals are not evictions
+    cache.pop();
+    cache.remove(&4);
+    assert_eq!(cache.stats().evictions, 3);
+
+    // Full `try_push` hands the pair back instead of evicting
+    cache.push(5, 5);
+    cache.push(6, 6);
+    assert_eq!(cache.try_push(7, 7), Err((7, 7)));
+    assert_eq!(cache.stats().evictions, 3);
+
+    if let Entry::Vacant(entry) = cache.entry(8) {
+        entry.insert_evict(8);
+    }
+    assert_eq!(cache.stats().evictions, 4);
+    assert_eq!(cache.stats().inserts, 8);
+}
+
+#[test]
+#[cfg(feature = "stats")]
+fn test_stats_count_spills_and_unspills() {
+    let mut cache = TinyLru::<i32, i32, 2>::with_capacity(8);
+    for i in 0..6 {
+        cache.push(i, i);
+    }
+    assert_eq!((cache.stats().spills, cache.stats().unspills), (1, 0));
+
+    // Failed unspill is not counted
+    assert!(!cache.unspill());
+    cache.pop();
+    cache.pop();
+    cache.pop();
+    cache.pop();
+    assert!(cache.unspill());
+    assert_eq!((cache.stats().spills, cache.stats().unspills), (1, 1));
+
+    // Policy-driven unspill after `pop`
+    cache.set_unspill_policy(UnspillPolicy::Eager);
+    cache.push(10, 10);
+    assert!(cache.is_spilled());
+    cache.pop();
+    assert!(!cache.is_spilled());
+    assert_eq!((cache.stats().spills, cache.stats().unspills), (2, 2));
+
+    // `resize` evicts down to the new capacity and unspills
+    for i in 20..28 {
+        cache.push(i, i);
+    }
+    let before = cache.stats();
+    assert_eq!(cache.resize(2).len(), 6);
+    let after = cache.stats();
+    assert_eq!(after.evictions - before.evictions, 6);
+    assert_eq!(after.unspills - before.unspills, 1);
+}
+
+#[test]
+#[cfg(feature = "stats")]
+fn test_stats_survive_clear_and_reset() {
+    let mut cache = TinyLru::<i32, i32, 2>::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.get(&0);
+    cache.clear();
+    cache.drain();
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.inserts, stats.spills), (1, 4, 1));
+    assert_eq!(cache.clone().stats(), stats);
+
+    cache.reset_stats();
+    assert_eq!(cache.stats(), CacheStats::default());
+    assert_eq!(cache.stats().hit_ratio(), 0.0);
+    cache.push(0, 0);
+    cache.peek(&0);
+    assert_eq!(cache.stats().hit_ratio(), 1.0);
+
+    // Resetting a snapshot leaves the cache's counters alone
+    let mut snapshot = cache.stats();
+    snapshot.reset();
+    assert_eq!(snapshot, CacheStats::default());
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+#[cfg(all(feature = "stats", target_has_atomic = "64"))]
+fn test_stats_keep_cache_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<TinyLru<u64, u64, 4>>();
+}
+
+// ============================================================================
+// EVICTION LISTENER TESTS
+// ============================================================================
+
+#[derive(Default)]
+struct Recorder(Vec<(i32, i32, EvictionReason)>);
+
+impl EvictionListener<i32, i32> for Recorder {
+    fn on_evict(&mut self, key: i32, value: i32, reason: EvictionReason) {
+        self.0.push((key, value, reason));
+    }
+}
+
+type Recorded<const N: usize> = TinyLru<i32, i32, N, FxBuildHasher, u16, Recorder>;
+
+#[test]
+fn test_listener_sees_capacity_evictions_and_replacements() {
+    let mut cache: Recorded<2> = TinyLru::with_capacity(3);
+    for i in 0..5 {
+        cache.push(i, i * 10);
+    }
+    cache.push(3, 31);
+    assert_eq!(
+        cache.listener().0,
+        [(0, 0, EvictionReason::Capacity), (1, 10, EvictionReason::Capacity), (3, 30, EvictionReason::Replaced)]
+    );
+    assert_eq!(cache.peek(&3), Some(&31));
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_listener_skips_pairs_returned_to_caller() {
+    let mut cache: Recorded<2> = TinyLru::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    assert_eq!(cache.push_evict(4, 4), Some((0, 0)));
+    assert_eq!(cache.push_evict(4, 5), Some((4, 4)));
+    assert_eq!(cache.try_push(1, 11), Ok(Some(1)));
+    assert_eq!(cache.try_push(9, 9), Err((9, 9)));
+    assert_eq!(cache.pop(), Some((2, 2)));
+    assert_eq!(cache.remove(&3), Some((3, 3)));
+    cache.push(6, 6);
+    cache.push(7, 7);
+    assert_eq!(cache.resize(2).len(), 2);
+    if let Entry::Vacant(entry) = cache.entry(8) {
+        assert!(entry.insert_evict(8).1.is_some());
+    }
+    assert_eq!(cache.drain().count(), 2);
+    assert!(cache.listener().0.is_empty());
+}
+
+#[test]
+fn test_listener_sees_vacant_entry_evictions() {
+    let mut cache: Recorded<2> = TinyLru::new();
+    *cache.entry(1).or_insert(0) += 1;
+    *cache.entry(2).or_insert(0) += 1;
+    *cache.entry(3).or_insert(0) += 1;
+    assert_eq!(cache.listener().0, [(1, 1, EvictionReason::Capacity)]);
+}
+
+#[test]
+fn test_listener_discard() {
+    let mut cache: Recorded<2> = TinyLru::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    assert!(cache.discard(&2));
+    assert!(!cache.discard(&2));
+    assert_eq!(cache.listener().0, [(2, 2, EvictionReason::Removed)]);
+    assert_eq!(cache.len(), 3);
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_listener_clear_in_lru_order() {
+    for cap in [3, 8] {
+        let mut cache: Recorded<3> = TinyLru::with_capacity(cap);
+        for i in 0..cap as i32 {
+            cache.push(i, i);
+        }
+        cache.get(&0);
+        cache.clear();
+
+        let mut expected: Vec<_> = (1..cap as i32).chain([0]).map(|i| (i, i, EvictionReason::Cleared)).collect();
+        assert_eq!(cache.listener().0, expected);
+        assert!(cache.is_empty());
+        assert!(!cache.is_spilled());
+        verify_dll_structure(&cache);
+
+        // The cache is fully usable after clearing
+        cache.listener_mut().0.clear();
+        for i in 0..cap as i32 + 1 {
+            cache.push(i, i);
+        }
+        expected = vec![(0, 0, EvictionReason::Capacity)];
+        assert_eq!(cache.listener().0, expected);
+        verify_dll_structure(&cache);
+    }
+}
+
+#[test]
+fn test_with_listener_keeps_entries() {
+    let mut cache = TinyLru::<i32, i32, 2>::with_capacity(4);
+    cache.set_unspill_policy(UnspillPolicy::Eager);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+
+    let mut seen = Vec::new();
+    let mut cache = cache.with_listener(|k, _, reason| seen.push((k, reason)));
+    assert_eq!(cache.len(), 4);
+    assert!(cache.is_spilled());
+    assert_eq!(cache.unspill_policy(), UnspillPolicy::Eager);
+    cache.push(4, 4);
+    assert_eq!(cache.get(&3), Some(&3));
+    cache.clear();
+    drop(cache);
+    assert_eq!(seen[0], (0, EvictionReason::Capacity));
+    assert_eq!(seen.len(), 5);
+}
+
+// ============================================================================
+// TTL TESTS
+// ============================================================================
+
+type TestClock = std::rc::Rc<core::cell::Cell<core::time::Duration>>;
+
+fn test_clock() -> (TestClock, impl Clock + Clone) {
+    let time = TestClock::default();
+    let handle = time.clone();
+    (time, move || handle.get())
+}
+
+fn ms(n: u64) -> core::time::Duration {
+    core::time::Duration::from_millis(n)
+}
+
+#[test]
+fn test_ttl_expired_entries_are_misses() {
+    let (time, clock) = test_clock();
+    let mut cache = TtlLru::<i32, i32, 4, _>::with_clock(clock);
+    cache.push_with_ttl(1, 10, ms(100));
+    cache.push_with_ttl(2, 20, ms(200));
+    cache.push(3, 30);
+
+    time.set(ms(99));
+    assert_eq!(cache.peek(&1), Some(&10));
+    assert!(cache.contains_key(&1));
+
+    time.set(ms(100));
+    assert_eq!(cache.peek(&1), None);
+    assert!(!cache.contains_key(&1));
+    assert_eq!(cache.len(), 3); // `peek` leaves it in place
+    assert_eq!(cache.get(&1), None);
+    assert_eq!(cache.len(), 2); // `get` drops it
+    assert_eq!(cache.get_mut(&2).map(|v| *v + 1), Some(21));
+
+    time.set(ms(1_000_000));
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.get(&3), Some(&30));
+    assert_eq!(cache.remove(&3), Some((3, 30)));
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_ttl_push_resets_deadline() {
+    let (time, clock) = test_clock();
+    let mut cache = TtlLru::<i32, i32, 4, _>::with_clock(clock);
+    cache.push_with_ttl(1, 10, ms(10));
+    time.set(ms(5));
+    cache.push_with_ttl(1, 11, ms(10));
+    time.set(ms(12));
+    assert_eq!(cache.peek(&1), Some(&11));
+    cache.push(1, 12);
+    time.set(ms(1_000));
+    assert_eq!(cache.peek(&1), Some(&12));
+
+    // A deadline that overflows never expires
+    cache.push_with_ttl(2, 20, core::time::Duration::MAX);
+    time.set(core::time::Duration::MAX);
+    assert_eq!(cache.peek(&2), Some(&20));
+}
+
+#[test]
+fn test_ttl_remove_and_pop_skip_expired() {
+    let (time, clock) = test_clock();
+    let mut cache = TtlLru::<i32, i32, 4, _>::with_clock(clock);
+    cache.push_with_ttl(1, 10, ms(5));
+    cache.push_with_ttl(2, 20, ms(5));
+    cache.push(3, 30);
+    cache.push_with_ttl(4, 40, ms(5));
+
+    time.set(ms(5));
+    assert_eq!(cache.remove(&4), None);
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.pop(), Some((3, 30)));
+    assert!(cache.is_empty());
+    assert_eq!(cache.pop(), None);
+}
+
+#[test]
+fn test_ttl_purge_expired() {
+    for cap in [8, 64] {
+        let (time, clock) = test_clock();
+        let mut cache = TtlLru::<i32, i32, 8, _>::with_capacity_and_clock(cap, clock);
+        for i in 0..cap as i32 {
+            if i % 3 == 0 {
+                cache.push(i, i);
+            } else {
+                cache.push_with_ttl(i, i, ms(i as u64));
+            }
+        }
+        assert_eq!(cache.is_spilled(), cap > 8);
+
+        time.set(ms(cap as u64 / 2));
+        let expired = (0..cap as i32).filter(|&i| i % 3 != 0 && i <= cap as i32 / 2).count();
+        assert_eq!(cache.purge_expired(), expired);
+        assert_eq!(cache.purge_expired(), 0);
+        assert_eq!(cache.len(), cap - expired);
+        for i in 0..cap as i32 {
+            let live = i % 3 == 0 || i > cap as i32 / 2;
+            assert_eq!(cache.peek(&i), live.then_some(&i));
+        }
+        verify_dll_structure(&cache.cache);
+        if cache.is_spilled() {
+            verify_index_consistency(&cache.cache);
+        }
+    }
+}
+
+#[test]
+fn test_ttl_evicts_lru_when_full() {
+    let (_time, clock) = test_clock();
+    let mut cache = TtlLru::<i32, i32, 2, _>::with_clock(clock);
+    cache.push_with_ttl(1, 10, ms(5));
+    cache.push(2, 20);
+    cache.get(&1);
+    cache.push(3, 30);
+    assert_eq!(cache.peek(&2), None);
+    assert_eq!(cache.peek(&1), Some(&10));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "stats")]
+fn test_ttl_stats_count_expired_as_misses() {
+    let (time, clock) = test_clock();
+    let mut cache = TtlLru::<i32, i32, 4, _>::with_clock(clock);
+    cache.push_with_ttl(1, 10, ms(5));
+    cache.push_with_ttl(2, 20, ms(5));
+    cache.get(&1);
+    time.set(ms(5));
+    cache.get(&1);
+    cache.peek(&2);
+    assert_eq!(cache.purge_expired(), 1);
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 2, 1));
+}
+
+#[test]
+fn test_std_clock_is_monotonic() {
+    let clock = StdClock::new();
+    let a = clock.now();
+    let b = clock.now();
+    assert!(b >= a);
+}
+
+// ============================================================================
+// WEIGHTED CAPACITY TESTS
+// ============================================================================
+
+#[allow(clippy::ptr_arg)] // Signature fixed by `Weigher<i32, Vec<u8>>`
+fn byte_len(_: &i32, v: &Vec<u8>) -> usize {
+    v.len()
+}
+
+type ByteLru<const N: usize> = WeightedLru<i32, Vec<u8>, N, fn(&i32, &Vec<u8>) -> usize>;
+
+fn check_weight<const N: usize>(cache: &ByteLru<N>) {
+    let sum: usize = cache.cache.values().map(|w| w.weight).sum();
+    assert_eq!(cache.total_weight(), sum);
+    assert!(sum <= cache.budget());
+    verify_dll_structure(&cache.cache);
+}
+
+#[test]
+fn test_weighted_evicts_lru_until_fit() {
+    let mut cache = ByteLru::<4>::with_budget(100, byte_len);
+    for i in 0..3 {
+        assert_eq!(cache.push(i, vec![0; 30]), Ok(None));
+    }
+    cache.get(&0);
+    // Needs 50: evicts 1 and 2 (LRU first), keeps 0
+    assert_eq!(cache.push(3, vec![0; 50]), Ok(None));
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains_key(&0) && cache.contains_key(&3));
+    assert_eq!(cache.total_weight(), 80);
+    check_weight(&cache);
+}
+
+#[test]
+fn test_weighted_rejects_oversized_entries() {
+    let mut cache = ByteLru::<4>::with_budget(64, byte_len);
+    cache.push(1, vec![1; 8]).unwrap();
+    assert_eq!(cache.push(2, vec![0; 65]), Err((2, vec![0; 65])));
+    // Rejected update leaves the existing entry alone
+    assert_eq!(cache.push(1, vec![0; 65]), Err((1, vec![0; 65])));
+    assert_eq!(cache.peek(&1), Some(&vec![1; 8]));
+
+    // An entry of exactly the budget fits, displacing everything else
+    assert_eq!(cache.push(3, vec![0; 64]), Ok(None));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.total_weight(), 64);
+}
+
+#[test]
+fn test_weighted_update_reweighs() {
+    let mut cache = ByteLru::<4>::with_budget(100, byte_len);
+    cache.push(1, vec![0; 30]).unwrap();
+    cache.push(2, vec![0; 30]).unwrap();
+    cache.push(3, vec![0; 30]).unwrap();
+
+    // Growing 1 to 60 needs 30 more: evicts 2 (LRU after the update promotes 1)
+    assert_eq!(cache.push(1, vec![0; 60]).map(|old| old.map(|v| v.len())), Ok(Some(30)));
+    assert_eq!(cache.len(), 2);
+    assert!(!cache.contains_key(&2));
+    assert_eq!(cache.total_weight(), 90);
+
+    // Shrinking frees budget without evicting
+    cache.push(1, vec![0; 10]).unwrap();
+    assert_eq!(cache.total_weight(), 40);
+    check_weight(&cache);
+}
+
+#[test]
+fn test_weighted_rejected_push_is_not_an_eviction() {
+    let mut cache = ByteLru::<2>::with_capacity_and_budget(2, 100, byte_len);
+    cache.push(1, vec![0; 10]).unwrap();
+    cache.push(2, vec![0; 20]).unwrap();
+    cache.cache.pin(&1);
+    cache.cache.pin(&2);
+
+    // Fits the budget, but the full inner cache has nothing it may evict
+    assert_eq!(cache.push(3, vec![0; 30]), Err((3, vec![0; 30])));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.total_weight(), 30);
+    assert!(!cache.contains_key(&3));
+    check_weight(&cache);
+
+    // Updates still go through
+    assert_eq!(cache.push(2, vec![0; 5]).map(|old| old.map(|v| v.len())), Ok(Some(20)));
+    assert_eq!(cache.total_weight(), 15);
+    check_weight(&cache);
+}
+
+#[test]
+fn test_weighted_respects_entry_capacity_and_spills() {
+    let mut cache = ByteLru::<2>::with_capacity_and_budget(4, 1_000, byte_len);
+    for i in 0..6 {
+        cache.push(i, vec![0; i as usize]).unwrap();
+    }
+    assert!(cache.is_spilled());
+    assert_eq!(cache.len(), 4);
+    assert_eq!(cache.total_weight(), 2 + 3 + 4 + 5);
+    check_weight(&cache);
+    verify_index_consistency(&cache.cache);
+
+    // Small entries only: stays inline
+    let mut small = ByteLru::<8>::with_budget(1_000, byte_len);
+    for i in 0..8 {
+        small.push(i, vec![0; 4]).unwrap();
+    }
+    assert!(!small.is_spilled());
+}
+
+#[test]
+fn test_weighted_remove_pop_and_budget() {
+    let mut cache = ByteLru::<4>::with_budget(100, byte_len);
+    for i in 0..5 {
+        cache.push(i, vec![0; 20]).unwrap();
+    }
+    assert_eq!(cache.pop().map(|(k, _)| k), Some(0));
+    assert_eq!(cache.remove(&2).map(|(k, v)| (k, v.len())), Some((2, 20)));
+    assert_eq!(cache.total_weight(), 60);
+
+    cache.set_budget(45);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.total_weight(), 40);
+    assert_eq!(cache.budget(), 45);
+    check_weight(&cache);
+
+    cache.get_mut(&4).unwrap().push(1);
+    assert_eq!(cache.total_weight(), 40); // Weighed on push only
+
+    cache.clear();
+    assert_eq!(cache.total_weight(), 0);
+    assert!(cache.is_empty());
+}
+
+#[test]
+#[cfg(feature = "stats")]
+fn test_weighted_stats_count_budget_evictions() {
+    let mut cache = ByteLru::<4>::with_budget(10, byte_len);
+    for i in 0..4 {
+        cache.push(i, vec![0; 5]).unwrap();
+    }
+    assert_eq!(cache.stats().evictions, 2);
+    assert_eq!(cache.stats().inserts, 4);
+}
+
+// ============================================================================
+// PINNED ENTRY TESTS
+// ============================================================================
+
+fn verify_pins<K: Eq + Hash, V, const N: usize, S: BuildHasher, I: LinkIndex, L: EvictionListener<K, V>>(
+    cache: &TinyLru<K, V, N, S, I, L>,
+) {
+    let pinned = (0..cache.len()).filter(|&i| cache.pins.contains(i)).count();
+    assert_eq!(pinned, cache.pinned_len());
+    assert!((cache.len()..cache.len() + 64).all(|i| !cache.pins.contains(i)));
+}
+
+#[test]
+fn test_pin_inline_slots_do_not_allocate() {
+    let mut cache = TinyLru::<i32, i32, 4>::with_capacity(8);
+    for i in 0..4 {
+        cache.push(i, i);
+        cache.pin(&i);
+    }
+    assert!(!cache.is_spilled());
+    assert!(!cache.pins.has_heap());
+    verify_pins(&cache);
+
+    // Only slots past `N` use the heap bits
+    cache.push(4, 4);
+    cache.push(5, 5);
+    assert!(cache.pin(&5));
+    assert!(cache.pins.has_heap());
+    verify_pins(&cache);
+
+    // Removing an inline slot pulls the spilled pin down into it
+    assert_eq!(cache.remove(&1), Some((1, 1)));
+    assert!(cache.is_pinned(&5));
+    assert_eq!(cache.pinned_len(), 4);
+    verify_pins(&cache);
+
+    cache.remove(&4);
+    assert!(cache.unspill());
+    assert!((0..4).chain([5]).filter(|k| *k != 1).all(|k| cache.is_pinned(&k)));
+    verify_pins(&cache);
+}
+
+#[test]
+fn test_pin_survives_eviction() {
+    for cap in [4, 16] {
+        let mut cache = TinyLru::<i32, i32, 4>::with_capacity(cap);
+        cache.push(0, 0);
+        cache.push(1, 1);
+        assert!(cache.pin(&0));
+        assert!(cache.pin(&0)); // Idempotent
+        assert!(!cache.pin(&99));
+        assert_eq!(cache.pinned_len(), 1);
+
+        for i in 2..100 {
+            cache.push(i, i);
+            verify_pins(&cache);
+        }
+        assert_eq!(cache.len(), cap);
+        assert!(cache.is_pinned(&0));
+        assert_eq!(cache.peek(&0), Some(&0));
+        assert!(!cache.contains_key(&1));
+        verify_dll_structure(&cache);
+        if cache.is_spilled() {
+            verify_index_consistency(&cache);
+        }
+    }
+}
+
+#[test]
+fn test_pin_pop_skips_pinned() {
+    let mut cache = TinyLru::<i32, i32, 4>::new();
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.pin(&0);
+    cache.pin(&2);
+    assert_eq!(cache.pop(), Some((1, 1)));
+    assert_eq!(cache.pop(), Some((3, 3)));
+    assert_eq!(cache.pop(), None);
+    assert_eq!(cache.len(), 2);
+    verify_pins(&cache);
+
+    assert!(cache.unpin(&2));
+    assert!(!cache.unpin(&2));
+    assert_eq!(cache.pop(), Some((2, 2)));
+    assert_eq!(cache.pinned_len(), 1);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_pin_moves_with_swapped_slot() {
+    let mut cache = TinyLru::<i32, i32, 4>::with_capacity(8);
+    for i in 0..8 {
+        cache.push(i, i);
+    }
+    // 7 sits in the last slot and is moved by every swap_remove below
+    cache.pin(&7);
+    cache.pin(&3);
+    cache.remove(&0);
+    cache.remove(&3);
+    assert!(cache.is_pinned(&7));
+    assert!(!cache.is_pinned(&3));
+    assert_eq!(cache.pinned_len(), 1);
+    verify_pins(&cache);
+
+    for i in 10..30 {
+        cache.push(i, i);
+    }
+    assert_eq!(cache.peek(&7), Some(&7));
+    verify_pins(&cache);
+    verify_post_spill_state(&cache);
+}
+
+#[test]
+fn test_pin_all_pinned_is_an_error() {
+    let mut cache = TinyLru::<i32, i32, 2>::new();
+    cache.push(1, 1);
+    cache.push(2, 2);
+    cache.pin(&1);
+    cache.pin(&2);
+    assert_eq!(cache.try_push_evict(3, 3), Err((3, 3)));
+    assert_eq!(cache.try_push_evict(2, 20), Ok(Some((2, 2))));
+    assert_eq!(cache.try_push(3, 3), Err((3, 3)));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_pin_push_rejects_when_all_pinned() {
+    let mut cache: Recorded<2> = TinyLru::new();
+    cache.push(1, 1);
+    cache.push(2, 2);
+    cache.pin(&1);
+    cache.pin(&2);
+
+    cache.push(3, 3);
+    assert_eq!(cache.listener().0, [(3, 3, EvictionReason::Rejected)]);
+    assert_eq!(cache.push_evict(4, 4), Some((4, 4)));
+    cache.push(2, 20); // Updates still work
+    assert_eq!(cache.listener().0[1], (2, 2, EvictionReason::Replaced));
+
+    let Entry::Vacant(entry) = cache.entry(5) else { panic!("expected vacant entry") };
+    assert_eq!(entry.try_insert(5), Err((5, 5)));
+    assert_eq!(*cache.entry(1).or_insert(10), 1);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains_key(&1) && cache.contains_key(&2));
+    verify_pins(&cache);
+
+    // Unpinning makes room again
+    cache.unpin(&1);
+    cache.push(3, 3);
+    assert!(!cache.contains_key(&1));
+    assert_eq!(cache.listener().0.last(), Some(&(1, 1, EvictionReason::Capacity)));
+}
+
+#[test]
+fn test_pin_vacant_entry_insert_evicts_when_all_pinned() {
+    let mut cache: Recorded<2> = TinyLru::new();
+    cache.push(1, 1);
+    cache.push(2, 2);
+    cache.pin(&1);
+    cache.pin(&2);
+
+    // Infallible inserts still make room: the LRU entry goes, pin and all
+    *cache.entry(3).or_insert(3) += 10;
+    assert_eq!(cache.listener().0, [(1, 1, EvictionReason::Capacity)]);
+    assert_eq!(cache.peek(&3), Some(&13));
+    assert!(cache.is_pinned(&2) && !cache.is_pinned(&3));
+    assert_eq!(cache.pinned_len(), 1);
+    verify_pins(&cache);
+
+    cache.pin(&3);
+    let Entry::Vacant(entry) = cache.entry(4) else { panic!("expected vacant entry") };
+    let (val, evicted) = entry.insert_evict(4);
+    assert_eq!((*val, evicted), (4, Some((2, 2))));
+    assert_eq!(cache.pinned_len(), 1);
+    verify_pins(&cache);
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_pin_resize_keeps_pinned() {
+    let mut cache = TinyLru::<i32, i32, 2>::with_capacity(8);
+    for i in 0..8 {
+        cache.push(i, i);
+    }
+    cache.pin(&0);
+    cache.pin(&1);
+    let evicted: Vec<_> = cache.resize(2).into_iter().map(|(k, _)| k).collect();
+    assert_eq!(evicted, [2, 3, 4, 5, 6, 7]);
+    assert!(!cache.is_spilled());
+    assert_eq!(cache.pinned_len(), 2);
+    verify_pins(&cache);
+}
+
+#[test]
+#[should_panic(expected = "pinned entries")]
+fn test_pin_resize_below_pinned_count() {
+    let mut cache = TinyLru::<i32, i32, 1>::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.pin(&0);
+    cache.pin(&1);
+    cache.resize(1);
+}
+
+#[test]
+fn test_pin_cleared_by_clear_and_drain() {
+    let mut cache = TinyLru::<i32, i32, 2>::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.pin(&3);
+    cache.clear();
+    assert_eq!(cache.pinned_len(), 0);
+
+    cache.push(5, 5);
+    cache.pin(&5);
+    assert_eq!(cache.drain().count(), 1);
+    assert_eq!(cache.pinned_len(), 0);
+    cache.push(6, 6);
+    assert!(!cache.is_pinned(&6));
+    verify_pins(&cache);
+}
+
+// ============================================================================
+// RECENCY CONTROL TESTS
+// ============================================================================
+
+fn order<const N: usize>(cache: &TinyLru<i32, i32, N>) -> Vec<i32> {
+    cache.keys().copied().collect()
+}
+
+#[test]
+fn test_peek_ends() {
+    for cap in [4, 8] {
+        let mut cache = TinyLru::<i32, i32, 4>::with_capacity(cap);
+        assert_eq!(cache.peek_lru(), None);
+        assert_eq!(cache.peek_mru(), None);
+        cache.push(1, 10);
+        assert_eq!(cache.peek_lru(), Some((&1, &10)));
+        assert_eq!(cache.peek_mru(), Some((&1, &10)));
+        for i in 2..=cap as i32 {
+            cache.push(i, i * 10);
+        }
+        cache.get(&1);
+        assert_eq!(cache.peek_lru(), Some((&2, &20)));
+        assert_eq!(cache.peek_mru(), Some((&1, &10)));
+        assert_eq!(cache.pop(), Some((2, 20)));
+    }
+}
+
+#[test]
+fn test_promote_and_demote() {
+    for cap in [5, 10] {
+        let mut cache = TinyLru::<i32, i32, 5>::with_capacity(cap);
+        for i in 0..5 {
+            cache.push(i, i);
+        }
+        assert!(cache.promote(&0));
+        assert_eq!(order(&cache), [1, 2, 3, 4, 0]);
+        assert!(cache.demote(&3));
+        assert_eq!(order(&cache), [3, 1, 2, 4, 0]);
+        assert!(cache.demote(&0)); // Tail to head
+        assert_eq!(order(&cache), [0, 3, 1, 2, 4]);
+        assert!(cache.demote(&0)); // Already head
+        assert!(cache.promote(&4)); // Already tail
+        assert_eq!(order(&cache), [0, 3, 1, 2, 4]);
+        assert!(!cache.promote(&99));
+        assert!(!cache.demote(&99));
+        verify_dll_structure(&cache);
+
+        // Demoted entries are evicted first
+        cache.set_capacity(cap.max(6));
+        for i in 5..cap.max(6) as i32 {
+            cache.push(i, i);
+        }
+        cache.demote(&4);
+        cache.push(100, 100);
+        assert!(!cache.contains_key(&4));
+        verify_dll_structure(&cache);
+        if cache.is_spilled() {
+            verify_index_consistency(&cache);
+        }
+    }
+}
+
+#[test]
+fn test_demote_single_entry() {
+    let mut cache = TinyLru::<i32, i32, 2>::new();
+    cache.push(1, 1);
+    assert!(cache.demote(&1));
+    assert_eq!(cache.peek_lru(), cache.peek_mru());
+    verify_dll_structure(&cache);
+}
+
+#[test]
+fn test_pop_mru() {
+    let mut cache = TinyLru::<i32, i32, 2>::with_capacity(6);
+    for i in 0..6 {
+        cache.push(i, i);
+    }
+    cache.get(&2);
+    assert_eq!(cache.pop_mru(), Some((2, 2)));
+    assert_eq!(cache.pop_mru(), Some((5, 5)));
+    verify_post_spill_state(&cache);
+
+    cache.pin(&4);
+    assert_eq!(cache.pop_mru(), Some((3, 3)));
+    assert_eq!(order(&cache), [0, 1, 4]);
+    verify_pins(&cache);
+
+    cache.set_unspill_policy(UnspillPolicy::Eager);
+    assert_eq!(cache.pop_mru(), Some((1, 1)));
+    assert!(!cache.is_spilled());
+    assert_eq!(cache.pop_mru(), Some((0, 0)));
+    assert_eq!(cache.pop_mru(), None);
+    assert_eq!(cache.len(), 1);
+    verify_dll_structure(&cache);
+}
+
+// ============================================================================
+// SLRU TESTS
+// ============================================================================
+
+/// Walk the shared list and check the segment split against the counters.
+fn verify_slru<K: Eq + Hash, V, const N: usize>(cache: &SlruCache<K, V, N>) {
+    verify_dll_structure(&cache.cache);
+    if cache.is_spilled() {
+        verify_index_consistency(&cache.cache);
+    }
+
+    let store = &cache.cache.store;
+    let mut cursor = cache.cache.head;
+    let mut probation = 0;
+    while cursor != u16::NONE && !store.val(cursor as usize).protected {
+        probation += 1;
+        cursor = store.next(cursor as usize);
+    }
+    assert_eq!(cursor, cache.boundary, "boundary must be the first protected node");
+    let mut protected = 0;
+    while cursor != u16::NONE {
+        assert!(store.val(cursor as usize).protected, "probation node after the boundary");
+        protected += 1;
+        cursor = store.next(cursor as usize);
+    }
+    assert_eq!(probation, cache.probation_len());
+    assert_eq!(protected, cache.protected_len());
+    assert!(protected <= cache.protected_capacity());
+}
+
+fn slru_order<const N: usize>(cache: &SlruCache<i32, i32, N>) -> Vec<i32> {
+    cache.cache.keys().copied().collect()
+}
+
+#[test]
+fn test_slru_second_hit_protects() {
+    let mut cache = SlruCache::<i32, i32, 4>::with_capacity_and_protected(4, 2);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    assert_eq!(cache.probation_len(), 4);
+    cache.get(&1);
+    cache.get(&3);
+    assert_eq!(slru_order(&cache), [0, 2, 1, 3]);
+    assert_eq!(cache.protected_len(), 2);
+    verify_slru(&cache);
+
+    // New keys go in front of the protected segment
+    cache.push(4, 4);
+    assert_eq!(slru_order(&cache), [2, 4, 1, 3]);
+    verify_slru(&cache);
+
+    // Overflowing protected demotes its LRU entry to probation MRU
+    cache.get(&2);
+    assert_eq!(slru_order(&cache), [4, 1, 3, 2]);
+    assert_eq!(cache.protected_len(), 2);
+    assert_eq!(cache.pop(), Some((4, 4)));
+    assert_eq!(cache.pop(), Some((1, 1)));
+    verify_slru(&cache);
+}
+
+#[test]
+fn test_slru_scan_resistance() {
+    for cap in [8, 64] {
+        let mut cache = SlruCache::<i32, i32, 8>::with_capacity(cap);
+        let hot = (cap * 3 / 5) as i32;
+        for i in 0..hot {
+            cache.push(i, i);
+            cache.get(&i);
+        }
+        for i in 1000..1000 + 10 * cap as i32 {
+            cache.push(i, i);
+            if i % 7 == 0 {
+                verify_slru(&cache);
+            }
+        }
+        for i in 0..hot {
+            assert_eq!(cache.peek(&i), Some(&i));
+        }
+        assert_eq!(cache.len(), cap);
+        assert_eq!(cache.is_spilled(), cap > 8);
+        verify_slru(&cache);
+
+        // Plain LRU loses the whole hot set to the same scan
+        let mut lru = TinyLru::<i32, i32, 8>::with_capacity(cap);
+        for i in 0..hot {
+            lru.push(i, i);
+            lru.get(&i);
+        }
+        for i in 1000..1000 + 10 * cap as i32 {
+            lru.push(i, i);
+        }
+        assert!((0..hot).all(|i| !lru.contains_key(&i)));
+    }
+}
+
+#[test]
+fn test_slru_remove_and_boundary_moves() {
+    let mut cache = SlruCache::<i32, i32, 4>::with_capacity_and_protected(8, 4);
+    for i in 0..8 {
+        cache.push(i, i);
+    }
+    for i in [6, 2, 7] {
+        cache.get(&i);
+    }
+    verify_slru(&cache);
+
+    // Remove the boundary node, the last slot, and a probation node
+    assert_eq!(cache.remove(&6), Some((6, 6)));
+    verify_slru(&cache);
+    assert_eq!(cache.remove(&7), Some((7, 7)));
+    verify_slru(&cache);
+    assert_eq!(cache.remove(&0), Some((0, 0)));
+    verify_slru(&cache);
+    assert_eq!(cache.remove(&0), None);
+
+    // Drain through pop: probation first, then protected
+    let mut popped = Vec::new();
+    while let Some((k, _)) = cache.pop() {
+        popped.push(k);
+        verify_slru(&cache);
+    }
+    assert_eq!(popped, [1, 3, 4, 5, 2]);
+}
+
+#[test]
+fn test_slru_update_and_protected_resize() {
+    let mut cache = SlruCache::<i32, i32, 4>::with_capacity_and_protected(4, 3);
+    for i in 0..4 {
+        cache.push(i, i);
+        cache.push(i, i * 10); // Update counts as the second touch
+    }
+    assert_eq!(cache.protected_len(), 3);
+    assert_eq!(cache.peek(&0), Some(&0));
+    assert_eq!(cache.get_mut(&3).map(|v| *v), Some(30));
+    verify_slru(&cache);
+
+    cache.set_protected_capacity(1);
+    assert_eq!(cache.protected_len(), 1);
+    verify_slru(&cache);
+    cache.set_protected_capacity(0);
+    cache.get(&1);
+    assert_eq!(cache.protected_len(), 0);
+    verify_slru(&cache);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    cache.push(9, 9);
+    cache.get(&9);
+    assert_eq!(cache.protected_len(), 0);
+    verify_slru(&cache);
+}
+
+#[test]
+fn test_slru_randomized_invariants() {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut cache = SlruCache::<i32, i32, 4>::with_capacity_and_protected(12, 5);
+    for step in 0..4000 {
+        let key = (next() % 24) as i32;
+        match next() % 5 {
+            0 | 1 => cache.push(key, step),
+            2 => {
+                cache.get(&key);
+            }
+            3 => {
+                cache.remove(&key);
+            }
+            _ => {
+                cache.pop();
+            }
+        }
+        verify_slru(&cache);
+    }
+}
+
+// ============================================================================
+// SIEVE TESTS
+// ============================================================================
+
+/// Check the list and index, and that the hand rests on a live node.
+fn verify_sieve<K: Eq + Hash, V, const N: usize>(cache: &SieveCache<K, V, N>) {
+    verify_dll_structure(&cache.cache);
+    if cache.is_spilled() {
+        verify_index_consistency(&cache.cache);
+    }
+    assert!(cache.hand == u16::NONE || (cache.hand as usize) < cache.len(), "hand points past the store");
+}
+
+fn sieve_order<const N: usize>(cache: &SieveCache<i32, i32, N>) -> Vec<i32> {
+    cache.cache.keys().copied().collect()
+}
+
+#[test]
+fn test_sieve_hand_skips_visited() {
+    let mut cache = SieveCache::<i32, i32, 4>::new();
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.get(&0);
+    cache.get(&2);
+
+    // Hits never reorder
+    assert_eq!(sieve_order(&cache), [0, 1, 2, 3]);
+
+    // Hand clears 0, evicts 1, and stays on 2
+    cache.push(4, 4);
+    assert_eq!(sieve_order(&cache), [0, 2, 3, 4]);
+    verify_sieve(&cache);
+
+    // Clears 2, evicts 3; 0 was cleared on the first pass and goes next
+    cache.push(5, 5);
+    assert_eq!(sieve_order(&cache), [0, 2, 4, 5]);
+    assert_eq!(cache.pop(), Some((4, 4)));
+    assert_eq!(cache.pop(), Some((5, 5)));
+    assert_eq!(cache.pop(), Some((0, 0)));
+    assert_eq!(cache.pop(), Some((2, 2)));
+    assert_eq!(cache.pop(), None);
+    verify_sieve(&cache);
+}
+
+#[test]
+fn test_sieve_all_visited_evicts_after_one_lap() {
+    let mut cache = SieveCache::<i32, i32, 4>::new();
+    for i in 0..4 {
+        cache.push(i, i);
+        cache.get(&i);
+    }
+    cache.push(4, 4);
+    assert!(!cache.contains_key(&0));
+    assert_eq!(cache.len(), 4);
+    verify_sieve(&cache);
+
+    // Updates mark the entry visited, peeks do not
+    cache.push(1, 10);
+    assert_eq!(cache.peek(&2), Some(&2));
+    cache.push(5, 5);
+    assert_eq!(cache.peek(&1), Some(&10));
+    assert!(!cache.contains_key(&2));
+    verify_sieve(&cache);
+}
+
+#[test]
+fn test_sieve_scan_resistance() {
+    for cap in [8, 64] {
+        let mut cache = SieveCache::<i32, i32, 8>::with_capacity(cap);
+        let hot = (cap / 2) as i32;
+        for i in 0..hot {
+            cache.push(i, i);
+        }
+        for i in 1000..1000 + 10 * cap as i32 {
+            for h in 0..hot {
+                cache.get(&h);
+            }
+            cache.push(i, i);
+        }
+        assert!((0..hot).all(|i| cache.contains_key(&i)));
+        assert_eq!(cache.len(), cap);
+        assert_eq!(cache.is_spilled(), cap > 8);
+        verify_sieve(&cache);
+    }
+}
+
+#[test]
+fn test_sieve_remove_moves_hand() {
+    let mut cache = SieveCache::<i32, i32, 4>::with_capacity(6);
+    for i in 0..6 {
+        cache.push(i, i);
+    }
+    cache.get(&0);
+    cache.push(6, 6); // Evicts 1, hand now on 2
+    verify_sieve(&cache);
+
+    // Removing the hand's node moves it on to 3
+    assert_eq!(cache.remove(&2), Some((2, 2)));
+    verify_sieve(&cache);
+    assert_eq!(cache.pop(), Some((3, 3)));
+
+    // Removing the node in the last slot keeps the hand on the same entry
+    assert_eq!(cache.remove(&6), Some((6, 6)));
+    assert_eq!(cache.remove(&6), None);
+    verify_sieve(&cache);
+    assert_eq!(cache.pop(), Some((4, 4)));
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.hand, u16::NONE);
+    cache.push(7, 7);
+    assert_eq!(cache.get_mut(&7).map(|v| *v), Some(7));
+    verify_sieve(&cache);
+}
+
+#[test]
+fn test_sieve_randomized_invariants() {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut cache = SieveCache::<i32, i32, 4>::with_capacity(12);
+    let mut model = std::collections::HashMap::new();
+    for step in 0..4000 {
+        let key = (next() % 24) as i32;
+        match next() % 5 {
+            0 | 1 => {
+                if cache.len() == cache.capacity() && !model.contains_key(&key) {
+                    // Whatever the hand picks must have been resident
+                    let before: Vec<i32> = model.keys().copied().collect();
+                    cache.push(key, step);
+                    let gone: Vec<i32> = before.into_iter().filter(|k| !cache.contains_key(k)).collect();
+                    assert_eq!(gone.len(), 1);
+                    model.remove(&gone[0]);
+                } else {
+                    cache.push(key, step);
+                }
+                model.insert(key, step);
+            }
+            2 => assert_eq!(cache.get(&key), model.get(&key)),
+            3 => assert_eq!(cache.remove(&key).map(|(_, v)| v), model.remove(&key)),
+            _ => {
+                if let Some((k, v)) = cache.pop() {
+                    assert_eq!(model.remove(&k), Some(v));
+                }
+            }
+        }
+        assert_eq!(cache.len(), model.len());
+        verify_sieve(&cache);
+    }
+}
+
+// ============================================================================
+// CLOCK TESTS
+// ============================================================================
+
+/// Check the hand and, post-spill, that every slot is indexed exactly once.
+fn verify_clock<K: Eq + Hash, V, const N: usize>(cache: &ClockCache<K, V, N>) {
+    assert!(cache.len() <= cache.capacity());
+    if cache.is_empty() {
+        assert_eq!(cache.hand, 0);
+    } else {
+        assert!((cache.hand as usize) < cache.len(), "hand points past the slots");
+    }
+    if let Some(index) = &cache.index {
+        assert_eq!(index.len(), cache.len());
+        for (i, slot) in cache.slots.iter().enumerate() {
+            assert_eq!(cache.find_key_index(&slot.key), Some(i));
+        }
+    }
+}
+
+fn clock_keys<const N: usize>(cache: &ClockCache<i32, i32, N>) -> Vec<i32> {
+    cache.slots.iter().map(|slot| slot.key).collect()
+}
+
+#[test]
+fn test_clock_second_chance() {
+    let mut cache = ClockCache::<i32, i32, 4>::new();
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.get(&0);
+    cache.get(&2);
+
+    // Hand clears 0, evicts 1 and writes 4 into its slot
+    assert_eq!(cache.push_evict(4, 4), Some((1, 1)));
+    assert_eq!(clock_keys(&cache), [0, 4, 2, 3]);
+    assert_eq!(cache.hand, 2);
+    verify_clock(&cache);
+
+    // Clears 2, evicts 3, then wraps round to 0, whose bit is now clear
+    assert_eq!(cache.push_evict(5, 5), Some((3, 3)));
+    assert_eq!(cache.push_evict(6, 6), Some((0, 0)));
+    assert_eq!(clock_keys(&cache), [6, 4, 2, 5]);
+    verify_clock(&cache);
+}
+
+#[test]
+fn test_clock_update_and_peek() {
+    let mut cache = ClockCache::<i32, i32, 3>::new();
+    for i in 0..3 {
+        cache.push(i, i);
+    }
+
+    // Updates set the bit and hand back the old value, peeks do not
+    assert_eq!(cache.push_evict(0, 10), Some((0, 0)));
+    assert_eq!(cache.peek(&1), Some(&1));
+    assert_eq!(cache.push_evict(3, 3), Some((1, 1)));
+    assert_eq!(cache.get_mut(&0).map(|v| *v), Some(10));
+    assert_eq!(cache.peek(&9), None);
+    assert!(!cache.contains_key(&1));
+
+    // Every bit set: one full lap, then the hand's starting slot goes
+    let mut cache = ClockCache::<i32, i32, 3>::new();
+    for i in 0..3 {
+        cache.push(i, i);
+        cache.get(&i);
+    }
+    assert_eq!(cache.push_evict(3, 3), Some((0, 0)));
+    verify_clock(&cache);
+}
+
+#[test]
+fn test_clock_push_evict_update_returns_old_value() {
+    let mut cache = ClockCache::<i32, &str, 2>::with_capacity(4);
+    cache.push(1, "a");
+    assert_eq!(cache.push_evict(1, "b"), Some((1, "a")));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.peek(&1), Some(&"b"));
+
+    // Same post-spill, without touching the index
+    for i in 2..5 {
+        cache.push(i, "x");
+    }
+    assert!(cache.is_spilled());
+    assert_eq!(cache.push_evict(3, "y"), Some((3, "x")));
+    assert_eq!(cache.len(), 4);
+    verify_clock(&cache);
+}
+
+#[test]
+fn test_clock_zero_capacity() {
+    let mut cache = ClockCache::<i32, i32, 0>::with_capacity(0);
+    assert_eq!(cache.push_evict(1, 1), None);
+    assert_eq!(cache.push_evict(2, 2), Some((1, 1)));
+    assert_eq!(cache.get(&2), Some(&2));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_clock_spill_semantics() {
+    let mut cache = ClockCache::<i32, i32, 4>::with_capacity(8);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    assert!(!cache.is_spilled());
+    cache.push(4, 4);
+    assert!(cache.is_spilled());
+    for i in 5..20 {
+        cache.push(i, i);
+        verify_clock(&cache);
+    }
+    assert_eq!(cache.len(), 8);
+    assert!(!cache.can_unspill());
+    assert!(!cache.unspill());
+
+    // Shrinking back into N evicts with the hand and returns inline
+    let evicted = cache.resize(4);
+    assert_eq!(evicted.len(), 4);
+    assert_eq!(cache.capacity(), 4);
+    assert!(!cache.is_spilled());
+    verify_clock(&cache);
+    for (k, _) in &evicted {
+        assert!(!cache.contains_key(k));
+    }
+
+    cache.set_capacity(6);
+    cache.push(100, 100);
+    cache.push(101, 101);
+    assert!(cache.is_spilled());
+    cache.remove(&100);
+    cache.remove(&101);
+    assert!(cache.unspill());
+    verify_clock(&cache);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert!(!cache.is_spilled());
+    verify_clock(&cache);
+}
+
+#[test]
+#[should_panic(expected = "new_cap must be > current size")]
+fn test_clock_set_capacity_below_len() {
+    let mut cache = ClockCache::<i32, i32, 2>::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.set_capacity(3);
+}
+
+#[test]
+fn test_clock_remove_moves_hand() {
+    let mut cache = ClockCache::<i32, i32, 4>::with_capacity(6);
+    for i in 0..6 {
+        cache.push(i, i);
+        cache.get(&i);
+    }
+    cache.get(&0);
+    // Full lap clears every bit and evicts slot 0; the hand stops on slot 1
+    assert_eq!(cache.push_evict(6, 6), Some((0, 0)));
+    assert_eq!(cache.hand, 1);
+
+    // Removing the last slot's entry while the hand is elsewhere leaves it be
+    assert_eq!(cache.remove(&5), Some((5, 5)));
+    assert_eq!(cache.hand, 1);
+
+    // Removing the hand's entry: the last entry moves in and is examined next
+    assert_eq!(cache.remove(&1), Some((1, 1)));
+    assert_eq!(clock_keys(&cache), [6, 4, 2, 3]);
+    assert_eq!(cache.pop(), Some((4, 4)));
+    verify_clock(&cache);
+
+    // A hand on the last slot follows its entry when another removal moves it
+    cache.push(7, 7);
+    cache.hand = 3;
+    let moved = clock_keys(&cache)[3];
+    cache.remove(&clock_keys(&cache)[0]);
+    assert_eq!(clock_keys(&cache)[cache.hand as usize], moved);
+    verify_clock(&cache);
+}
+
+#[test]
+fn test_clock_randomized_invariants() {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut cache = ClockCache::<i32, i32, 4>::with_capacity(12);
+    let mut model = std::collections::HashMap::new();
+    for step in 0..4000 {
+        let key = (next() % 24) as i32;
+        match next() % 6 {
+            0 | 1 => {
+                if let Some((k, v)) = cache.push_evict(key, step) {
+                    assert_eq!(model.remove(&k), Some(v));
+                }
+                model.insert(key, step);
+            }
+            2 => assert_eq!(cache.get(&key), model.get(&key)),
+            3 => assert_eq!(cache.remove(&key).map(|(_, v)| v), model.remove(&key)),
+            4 => {
+                if let Some((k, v)) = cache.pop() {
+                    assert_eq!(model.remove(&k), Some(v));
+                }
+            }
+            _ => {
+                cache.unspill();
+            }
+        }
+        assert_eq!(cache.len(), model.len());
+        verify_clock(&cache);
+    }
+}
+
+// ============================================================================
+// ARC TESTS
+// ============================================================================
+
+/// Walk the resident list, check the T1/T2 split, and check the ARC directory bounds.
+fn verify_arc<const N: usize>(cache: &ArcCache<i32, i32, N>) {
+    verify_dll_structure(&cache.cache);
+    if cache.is_spilled() {
+        verify_index_consistency(&cache.cache);
+    }
+
+    let store = &cache.cache.store;
+    let mut cursor = cache.cache.head;
+    let mut recent = 0;
+    while cursor != u16::NONE && !store.val(cursor as usize).frequent {
+        recent += 1;
+        cursor = store.next(cursor as usize);
+    }
+    assert_eq!(cursor, cache.boundary, "boundary must be the first T2 node");
+    let mut frequent = 0;
+    while cursor != u16::NONE {
+        assert!(store.val(cursor as usize).frequent, "T1 node after the boundary");
+        frequent += 1;
+        cursor = store.next(cursor as usize);
+    }
+    assert_eq!(recent, cache.recent_len());
+    assert_eq!(frequent, cache.frequent_len());
+
+    let cap = cache.capacity();
+    let (b1, b2) = cache.ghost_len();
+    assert!(cache.len() <= cap);
+    assert!(cache.recent_target() <= cap);
+    assert!(recent + b1 <= cap, "|T1| + |B1| must stay within c");
+    assert!(cache.len() + b1 + b2 <= 2 * cap, "directory must stay within 2c");
+    for key in cache.ghost_recent.keys().chain(cache.ghost_frequent.keys()) {
+        assert!(!cache.contains_key(key), "ghost key {key} is also resident");
+    }
+    assert!(cache.ghost_recent.keys().all(|k| !cache.ghost_frequent.contains_key(k)));
+}
+
+#[test]
+fn test_arc_lists_and_ghosts() {
+    let mut cache = ArcCache::<i32, i32, 4>::new();
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.get(&1);
+    cache.get(&2);
+    assert_eq!((cache.recent_len(), cache.frequent_len()), (2, 2));
+    assert_eq!(cache.cache.keys().copied().collect::<Vec<_>>(), [0, 3, 1, 2]);
+    verify_arc(&cache);
+
+    // T1 is over its target (p = 0): its LRU goes to B1
+    cache.push(4, 4);
+    assert!(!cache.contains_key(&0));
+    assert_eq!(cache.ghost_len(), (1, 0));
+    verify_arc(&cache);
+
+    // B1 hit: p grows and the key comes back into T2
+    cache.push(0, 0);
+    assert_eq!(cache.recent_target(), 1);
+    assert_eq!(cache.frequent_len(), 3);
+    assert_eq!(cache.ghost_len(), (1, 0));
+    verify_arc(&cache);
+
+    // T1 == p now, so T2's LRU is evicted into B2; a B2 hit shrinks p again
+    cache.push(5, 5);
+    assert_eq!(cache.ghost_len().1, 1);
+    let ghost = *cache.ghost_frequent.keys().next().unwrap();
+    cache.push(ghost, ghost);
+    assert_eq!(cache.recent_target(), 0);
+    assert!(cache.contains_key(&ghost));
+    verify_arc(&cache);
+}
+
+#[test]
+fn test_arc_scan_keeps_frequent_set() {
+    for cap in [8, 64] {
+        let mut cache = ArcCache::<i32, i32, 8>::with_capacity(cap);
+        let hot = (cap / 2) as i32;
+        for i in 0..hot {
+            cache.push(i, i);
+            cache.get(&i);
+        }
+        for i in 1000..1000 + 10 * cap as i32 {
+            cache.push(i, i);
+            if i % 7 == 0 {
+                verify_arc(&cache);
+            }
+        }
+        assert!((0..hot).all(|i| cache.contains_key(&i)));
+        assert_eq!(cache.len(), cap);
+        assert_eq!(cache.is_spilled(), cap > 8);
+        verify_arc(&cache);
+    }
+}
+
+#[test]
+fn test_arc_adapts_to_recency() {
+    // A loop slightly larger than T2's share keeps hitting B1 ghosts, which pulls p up
+    let mut cache = ArcCache::<i32, i32, 8>::with_capacity(16);
+    for i in 0..8 {
+        cache.push(i, i);
+        cache.get(&i);
+    }
+    for round in 0..20 {
+        for i in 100..112 {
+            if cache.get(&i).is_none() {
+                cache.push(i, round);
+            }
+        }
+        verify_arc(&cache);
+    }
+    assert!(cache.recent_target() > 0);
+    assert!((100..112).filter(|i| cache.contains_key(i)).count() >= 8);
+}
+
+#[test]
+fn test_arc_update_remove_pop_clear() {
+    let mut cache = ArcCache::<i32, i32, 4>::with_capacity(6);
+    for i in 0..6 {
+        cache.push(i, i);
+    }
+    cache.push(2, 20); // Update counts as a hit
+    assert_eq!(cache.frequent_len(), 1);
+    assert_eq!(cache.peek(&2), Some(&20));
+    assert_eq!(cache.get_mut(&3).map(|v| *v), Some(3));
+    verify_arc(&cache);
+
+    // Remove the boundary node and a T1 node
+    assert_eq!(cache.remove(&2), Some((2, 20)));
+    verify_arc(&cache);
+    assert_eq!(cache.remove(&0), Some((0, 0)));
+    assert_eq!(cache.remove(&0), None);
+    verify_arc(&cache);
+
+    // pop follows the eviction choice: T1 first while it is over p
+    let mut popped = Vec::new();
+    while let Some((k, _)) = cache.pop() {
+        popped.push(k);
+        verify_arc(&cache);
+    }
+    assert_eq!(popped, [1, 4, 5, 3]);
+    assert_eq!(cache.ghost_len(), (0, 0));
+
+    for i in 0..10 {
+        cache.push(i, i);
+    }
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.ghost_len(), (0, 0));
+    assert_eq!(cache.recent_target(), 0);
+    verify_arc(&cache);
+}
+
+#[test]
+fn test_arc_set_capacity() {
+    let mut cache = ArcCache::<i32, i32, 4>::with_capacity(12);
+    for i in 0..40 {
+        cache.push(i % 30, i);
+        if i % 3 == 0 {
+            cache.get(&(i % 30));
+        }
+    }
+    verify_arc(&cache);
+    assert!(cache.is_spilled());
+
+    for _ in 0..6 {
+        cache.pop();
+    }
+    cache.set_capacity(8);
+    assert_eq!(cache.capacity(), 8);
+    verify_arc(&cache);
+
+    cache.set_capacity(20);
+    for i in 100..140 {
+        cache.push(i, i);
+        verify_arc(&cache);
+    }
+    assert_eq!(cache.len(), 20);
+}
+
+#[test]
+#[should_panic(expected = "new_cap must be > current size")]
+fn test_arc_set_capacity_below_len() {
+    let mut cache = ArcCache::<i32, i32, 2>::with_capacity(4);
+    for i in 0..4 {
+        cache.push(i, i);
+    }
+    cache.set_capacity(3);
+}
+
+#[test]
+fn test_arc_randomized_invariants() {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut cache = ArcCache::<i32, i32, 4>::with_capacity(12);
+    for step in 0..6000 {
+        // Alternate between a wide key space and a narrow one
+        let space = if (step / 1000) % 2 == 0 { 48 } else { 16 };
+        let key = (next() % space) as i32;
+        match next() % 6 {
+            0..=2 => cache.push(key, step),
+            3 => {
+                cache.get(&key);
+            }
+            4 => {
+                cache.remove(&key);
+            }
+            _ => {
+                cache.pop();
+            }
+        }
+        verify_arc(&cache);
+    }
+}
+
+// ============================================================================
+// ADMISSION FILTER TESTS
+// ============================================================================
+
+#[cfg(feature = "admission")]
+#[test]
+fn test_admission_off_by_default() {
+    let mut cache = TinyLru::<i32, i32, 2>::new();
+    assert!(cache.admission().is_none());
+    cache.push(1, 1);
+    cache.push(2, 2);
+    cache.get(&1);
+    cache.get(&2);
+    assert_eq!(cache.push_evict(3, 3), Some((1, 1)));
+}
+
+#[cfg(feature = "admission")]
+#[test]
+fn test_admission_rejects_one_hit_wonders() {
+    for cap in [8usize, 256] {
+        let mut cache = TinyLru::<i32, i32, 8>::with_capacity(cap);
+        cache.set_admission(Some(TinyLfu::new(cap)));
+        let hot = cap as i32;
+        for _ in 0..3 {
+            for i in 0..hot {
+                if cache.get(&i).is_none() {
+                    cache.push(i, i);
+                }
+            }
+        }
+        assert_eq!(cache.len(), cap);
+
+        // A scan of fresh keys: each is seen once and loses to the hot victim, bar the
+        // odd sketch collision that overestimates one
+        for i in 10_000..10_000 + 4 * hot {
+            cache.push(i, i);
+        }
+        let filter = cache.admission().unwrap();
+        let admitted = filter.admitted() as usize;
+        assert!(admitted <= cap / 64, "{admitted} scan keys admitted");
+        assert_eq!(filter.rejected() as usize, 4 * cap - admitted);
+        assert_eq!((0..hot).filter(|i| cache.contains_key(i)).count(), cap - admitted);
+        assert_eq!(cache.is_spilled(), cap > 8);
+        verify_dll_structure(&cache);
+        if cache.is_spilled() {
+            verify_index_consistency(&cache);
+        }
+
+        // Without the filter the same scan flushes the hot set
+        cache.set_admission(None);
+        for i in 10_000..10_000 + 4 * hot {
+            cache.push(i, i);
+        }
+        assert!((0..hot).all(|i| !cache.contains_key(&i)));
+    }
+}
+
+#[cfg(feature = "admission")]
+#[test]
+fn test_admission_reports_decisions() {
+    let mut cache: Recorded<2> = TinyLru::new();
+    cache.set_admission(Some(TinyLfu::new(2)));
+    cache.push(1, 10);
+    cache.push(2, 20);
+    cache.push(3, 30);
+    assert_eq!(cache.listener().0, [(3, 30, EvictionReason::Rejected)]);
+
+    // Pushed again: past the doorkeeper and hotter than the victim
+    cache.push(3, 31);
+    assert_eq!(cache.listener().0[1], (1, 10, EvictionReason::Capacity));
+    assert_eq!(cache.peek(&3), Some(&31));
+
+    // try_push_evict hands a rejected pair back; updates are never filtered
+    assert_eq!(cache.try_push_evict(4, 40), Err((4, 40)));
+    assert_eq!(cache.try_push_evict(2, 21), Ok(Some((2, 20))));
+    assert_eq!(cache.listener().0.len(), 2);
+
+    let filter = cache.admission().unwrap();
+    assert_eq!((filter.admitted(), filter.rejected()), (1, 2));
+}
+
+#[cfg(feature = "admission")]
+#[test]
+fn test_admission_counts_try_push_accesses() {
+    let mut cache = TinyLru::<u32, &str, 2>::new();
+    cache.set_admission(Some(TinyLfu::new(2)));
+    cache.push(1, "a");
+    cache.push(2, "b");
+
+    // Refused for lack of room, but still seen once
+    assert_eq!(cache.try_push(3, "c"), Err((3, "c")));
+    assert_eq!(cache.push_evict(3, "c"), Some((1, "a")));
+    assert_eq!(cache.admission().map(|f| (f.admitted(), f.rejected())), Some((1, 0)));
+}
+
+#[cfg(feature = "admission")]
+#[test]
+fn test_admission_compares_against_unpinned_victim() {
+    let mut cache = TinyLru::<i32, i32, 3>::new();
+    cache.set_admission(Some(TinyLfu::new(3)));
+    cache.push(1, 1);
+    for _ in 0..4 {
+        cache.get(&1);
+    }
+    cache.push(2, 2);
+    cache.push(3, 3);
+    cache.pin(&1);
+
+    // The head (1) is pinned and hot; the real victim, 2, was seen once
+    cache.push(4, 4);
+    cache.push(4, 4);
+    assert!(cache.contains_key(&4));
+    assert!(!cache.contains_key(&2));
+
+    // Everything pinned: the filter steps aside and `try_push_evict` reports it
+    cache.pin(&3);
+    cache.pin(&4);
+    assert_eq!(cache.try_push_evict(5, 5), Err((5, 5)));
+}
+
+#[cfg(feature = "admission")]
+#[test]
+fn test_admission_sketch_saturates_and_ages() {
+    let mut filter = TinyLfu::with_sample_size(64, 1000);
+    let hash = 0x1234_5678_9abc_def0;
+    filter.record(hash);
+    assert_eq!(filter.estimate(hash), 1); // Doorkeeper only
+    for _ in 0..100 {
+        filter.record(hash);
+    }
+    assert_eq!(filter.estimate(hash), 16); // 15 in the sketch, 1 in the doorkeeper
+
+    // Reaching the sample size halves the counters and clears the doorkeeper
+    for i in 0..(1000 - 101) as u64 {
+        filter.record(i.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    }
+    assert!(filter.estimate(hash) <= 8);
+    assert!(filter.estimate(hash) >= 7);
+
+    filter.clear();
+    assert_eq!(filter.estimate(hash), 0);
+    assert_eq!(filter.sample_size(), 1000);
+}
+
+#[cfg(feature = "admission")]
+#[test]
+fn test_admission_adapts_after_aging() {
+    // An old favourite fades, and a new hot key wins its slot back
+    let mut cache = TinyLru::<i32, i32, 2>::new();
+    cache.set_admission(Some(TinyLfu::with_sample_size(2, 40)));
+    cache.push(1, 1);
+    cache.push(2, 2);
+    for _ in 0..8 {
+        cache.get(&1);
+        cache.get(&2);
+    }
+    cache.push(3, 3);
+    assert!(!cache.contains_key(&3));
+
+    let mut admitted = false;
+    for _ in 0..40 {
+        cache.push(3, 3);
+        if cache.contains_key(&3) {
+            admitted = true;
+            break;
+        }
+    }
+    assert!(admitted);
+    assert_eq!(cache.len(), 2);
+}
@@ -0,0 +1,355 @@
+//! Per-entry time-to-live on top of [`TinyLru`](crate::TinyLru).
+//!
+//! [`TtlLru`] stores a deadline next to each value and reads the time from a [`Clock`].
+//! Expired entries stay in place until something touches them: lookups treat them as
+//! misses and drop them, and [`purge_expired`](TtlLru::purge_expired) sweeps them all.
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use core::time::Duration;
+
+use crate::{FxBuildHasher, LinkIndex, TinyLru};
+
+/// Monotonic time source for a [`TtlLru`].
+///
+/// `now` is the time elapsed since a fixed origin of the clock's choosing; it must never
+/// go backwards. Implemented for closures returning a `Duration`, which makes a manual
+/// clock for tests or a hardware timer on `no_std` targets a one-liner.
+pub trait Clock {
+    /// Current time since the clock's origin.
+    fn now(&self) -> Duration;
+}
+
+impl<F: Fn() -> Duration> Clock for F {
+    #[inline]
+    fn now(&self) -> Duration {
+        self()
+    }
+}
+
+/// [`Clock`] backed by `std::time::Instant`, with its origin at creation.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct StdClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl StdClock {
+    /// Create a clock whose origin is now.
+    #[inline]
+    pub fn new() -> Self {
+        Self { origin: std::time::Instant::now() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A value and the time after which it is stale (`None`: never).
+#[derive(Clone)]
+pub(crate) struct Timed<V> {
+    value: V,
+    deadline: Option<Duration>,
+}
+
+impl<V> Timed<V> {
+    #[inline(always)]
+    fn is_expired(&self, now: Duration) -> bool {
+        matches!(self.deadline, Some(deadline) if deadline <= now)
+    }
+}
+
+/// LRU cache whose entries can expire after a time-to-live.
+///
+/// Behaves like [`TinyLru`] (same inline-then-spill storage and eviction order), except
+/// that entries pushed with [`push_with_ttl`](Self::push_with_ttl) become misses once
+/// their deadline passes. Expired entries still occupy a slot, and count towards
+/// [`len`](Self::len), until a lookup, [`pop`](Self::pop) or
+/// [`purge_expired`](Self::purge_expired) drops them.
+///
+/// # Example
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use std::time::Duration;
+/// use tiny_lru::TtlLru;
+///
+/// let time = Rc::new(Cell::new(Duration::ZERO));
+/// let clock = { let time = time.clone(); move || time.get() };
+///
+/// let mut cache = TtlLru::<&str, u32, 4, _>::with_clock(clock);
+/// cache.push_with_ttl("session", 1, Duration::from_millis(50));
+/// cache.push("config", 2); // Never expires
+///
+/// time.set(Duration::from_millis(49));
+/// assert_eq!(cache.get(&"session"), Some(&1));
+/// time.set(Duration::from_millis(50));
+/// assert_eq!(cache.get(&"session"), None);
+/// assert_eq!(cache.get(&"config"), Some(&2));
+/// ```
+#[derive(Clone)]
+pub struct TtlLru<K, V, const N: usize, C, S = FxBuildHasher, I = u16> {
+    pub(crate) cache: TinyLru<K, Timed<V>, N, S, I>,
+    clock: C,
+}
+
+impl<K, V, const N: usize, C, I> TtlLru<K, V, N, C, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    C: Clock,
+    I: LinkIndex,
+{
+    /// Create a new cache with capacity = N, reading time from `clock`.
+    #[inline]
+    pub fn with_clock(clock: C) -> Self {
+        Self::with_capacity_and_clock(N, clock)
+    }
+
+    /// Create a new cache with the given capacity (must be >= N), reading time from `clock`.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity_and_clock(cap: usize, clock: C) -> Self {
+        Self::with_capacity_clock_and_hasher(cap, clock, FxBuildHasher)
+    }
+}
+
+impl<K, V, const N: usize, C, S, I> TtlLru<K, V, N, C, S, I>
+where
+    K: Eq + Hash,
+    C: Clock,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    /// Create a new cache with the given capacity, clock and post-spill hasher.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity_clock_and_hasher(cap: usize, clock: C, hash_builder: S) -> Self {
+        Self { cache: TinyLru::with_capacity_and_hasher(cap, hash_builder), clock }
+    }
+
+    /// Returns a reference to the clock.
+    #[inline]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Insert or update an entry that never expires, promoting it to MRU.
+    ///
+    /// Replaces any deadline the key had. Evicts the LRU entry, expired or not, if the
+    /// cache is full.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) {
+        self.cache.push(key, Timed { value, deadline: None });
+    }
+
+    /// Insert or update an entry that expires `ttl` from now, promoting it to MRU.
+    ///
+    /// A `ttl` too large to represent as a deadline means the entry never expires.
+    #[inline]
+    pub fn push_with_ttl(&mut self, key: K, value: V, ttl: Duration) {
+        let deadline = self.clock.now().checked_add(ttl);
+        self.cache.push(key, Timed { value, deadline });
+    }
+
+    /// Get a live value by key, promoting it to MRU.
+    ///
+    /// An expired entry is dropped and reported as a miss.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_live(key)?;
+        self.cache.promote_to_mru(index);
+        Some(&self.cache.store.val(index).value)
+    }
+
+    /// Get a mutable reference to a live value by key, promoting it to MRU.
+    ///
+    /// An expired entry is dropped and reported as a miss.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find_live(key)?;
+        self.cache.promote_to_mru(index);
+        Some(&mut self.cache.store.val_mut(index).value)
+    }
+
+    /// Peek at a live value by key without promoting it.
+    ///
+    /// Returns `None` for an expired entry, which stays in place until purged.
+    #[inline]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let now = self.clock.now();
+        let timed = self
+            .cache
+            .find_key_index(key)
+            .map(|index| self.cache.store.val(index))
+            .filter(|timed| !timed.is_expired(now));
+        self.cache.counters.peek(timed.is_some());
+        timed.map(|timed| &timed.value)
+    }
+
+    /// Returns `true` if the key is present and not expired. Does not promote.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let now = self.clock.now();
+        self.cache
+            .find_key_index(key)
+            .is_some_and(|index| !self.cache.store.val(index).is_expired(now))
+    }
+
+    /// Remove a live entry and return it. An expired entry is dropped and `None` returned.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.cache.find_key_index(key)?;
+        let now = self.clock.now();
+        let (key, timed) = self.cache.remove_at(index);
+        (!timed.is_expired(now)).then_some((key, timed.value))
+    }
+
+    /// Remove and return the least recently used live entry, dropping any expired
+    /// entries in front of it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let now = self.clock.now();
+        while let Some((key, timed)) = self.cache.pop() {
+            if !timed.is_expired(now) {
+                return Some((key, timed.value));
+            }
+        }
+        None
+    }
+
+    /// Drop every expired entry, returning how many were removed.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use tiny_lru::TtlLru;
+    ///
+    /// let mut cache = TtlLru::<u32, u32, 8, _>::with_clock(|| Duration::from_secs(10));
+    /// cache.push_with_ttl(1, 1, Duration::ZERO);
+    /// cache.push_with_ttl(2, 2, Duration::from_secs(5));
+    /// cache.push(3, 3);
+    ///
+    /// assert_eq!(cache.len(), 3);
+    /// assert_eq!(cache.purge_expired(), 1);
+    /// assert_eq!(cache.len(), 2);
+    /// ```
+    pub fn purge_expired(&mut self) -> usize {
+        let now = self.clock.now();
+        let mut purged = 0;
+        // Walk slots downwards: `remove_at` swaps the last slot into the freed one,
+        // and every slot above `i` has already been checked.
+        for i in (0..self.cache.store.len()).rev() {
+            if self.cache.store.val(i).is_expired(now) {
+                self.cache.remove_at(i);
+                self.cache.counters.evict();
+                purged += 1;
+            }
+        }
+        purged
+    }
+
+    /// Remove all entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Number of entries, including expired ones not yet dropped.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if there are no entries, live or expired.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the maximum number of entries.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+
+    /// Returns `true` if the entries have spilled to the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        self.cache.is_spilled()
+    }
+
+    /// Returns a snapshot of the operation counters (`stats` feature).
+    ///
+    /// Expired entries found by a lookup count as misses; entries dropped by
+    /// `purge_expired` count as evictions.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> crate::CacheStats {
+        self.cache.stats()
+    }
+
+    /// Zero all operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.cache.reset_stats();
+    }
+
+    /// Find a live entry for a promoting lookup, dropping it if expired.
+    #[inline(always)]
+    fn find_live<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(index) = self.cache.find_key_index(key) else {
+            self.cache.counters.lookup(false);
+            return None;
+        };
+        if self.cache.store.val(index).is_expired(self.clock.now()) {
+            self.cache.counters.lookup(false);
+            self.cache.remove_at(index);
+            return None;
+        }
+        self.cache.counters.lookup(true);
+        Some(index)
+    }
+}
@@ -0,0 +1,280 @@
+//! Weight-bounded LRU on top of [`TinyLru`](crate::TinyLru).
+//!
+//! [`WeightedLru`] charges each entry a weight from a [`Weigher`] (typically its size in
+//! bytes) and evicts from the LRU end until the total fits a budget. The weight is
+//! computed once on insert and stored next to the value, so evictions never call the
+//! weigher again.
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+use crate::{FxBuildHasher, LinkIndex, TinyLru};
+
+/// Computes the cost of an entry for a [`WeightedLru`].
+///
+/// Implemented for closures `Fn(&K, &V) -> usize`.
+pub trait Weigher<K, V> {
+    /// Weight charged against the budget while the entry is cached.
+    fn weight(&self, key: &K, value: &V) -> usize;
+}
+
+impl<K, V, F: Fn(&K, &V) -> usize> Weigher<K, V> for F {
+    #[inline]
+    fn weight(&self, key: &K, value: &V) -> usize {
+        self(key, value)
+    }
+}
+
+/// A value and the weight it was charged on insert.
+#[derive(Clone)]
+pub(crate) struct Weighted<V> {
+    value: V,
+    pub(crate) weight: usize,
+}
+
+/// LRU cache bounded by the total weight of its entries rather than their count.
+///
+/// Storage is a [`TinyLru`], so a handful of small entries still live inline and only
+/// spill once there are more than `N`. An optional entry-count capacity applies on top
+/// of the budget.
+///
+/// Weights are fixed when an entry is pushed; changing a value through
+/// [`get_mut`](Self::get_mut) does not re-weigh it. Push the value again instead.
+///
+/// # Example
+/// ```
+/// use tiny_lru::WeightedLru;
+///
+/// let mut cache = WeightedLru::<u32, Vec<u8>, 4, _>::with_budget(100, |_: &u32, v: &Vec<u8>| v.len());
+/// cache.push(1, vec![0; 40]).unwrap();
+/// cache.push(2, vec![0; 40]).unwrap();
+/// cache.push(3, vec![0; 40]).unwrap(); // Evicts 1 to stay within 100
+/// assert_eq!(cache.total_weight(), 80);
+/// assert!(!cache.contains_key(&1));
+///
+/// // Heavier than the whole budget: handed back untouched
+/// assert_eq!(cache.push(4, vec![0; 101]).map_err(|(k, v)| (k, v.len())), Err((4, 101)));
+/// ```
+#[derive(Clone)]
+pub struct WeightedLru<K, V, const N: usize, W, S = FxBuildHasher, I = u16> {
+    pub(crate) cache: TinyLru<K, Weighted<V>, N, S, I>,
+    weigher: W,
+    budget: usize,
+    total: usize,
+}
+
+impl<K, V, const N: usize, W, I> WeightedLru<K, V, N, W, FxBuildHasher, I>
+where
+    K: Eq + Hash,
+    W: Weigher<K, V>,
+    I: LinkIndex,
+{
+    /// Create a cache holding entries up to a total weight of `budget`.
+    ///
+    /// The entry count is only limited by `I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_budget(budget: usize, weigher: W) -> Self {
+        Self::with_capacity_and_budget(I::MAX_CAPACITY, budget, weigher)
+    }
+
+    /// Create a cache holding at most `cap` entries (must be >= N) up to a total weight
+    /// of `budget`.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity_and_budget(cap: usize, budget: usize, weigher: W) -> Self {
+        Self::with_capacity_budget_and_hasher(cap, budget, weigher, FxBuildHasher)
+    }
+}
+
+impl<K, V, const N: usize, W, S, I> WeightedLru<K, V, N, W, S, I>
+where
+    K: Eq + Hash,
+    W: Weigher<K, V>,
+    S: BuildHasher,
+    I: LinkIndex,
+{
+    /// Create a cache with the given entry capacity, weight budget and post-spill hasher.
+    ///
+    /// # Panics
+    /// Panics if `cap < N` or `cap > I::MAX_CAPACITY`.
+    #[inline]
+    pub fn with_capacity_budget_and_hasher(cap: usize, budget: usize, weigher: W, hash_builder: S) -> Self {
+        Self { cache: TinyLru::with_capacity_and_hasher(cap, hash_builder), weigher, budget, total: 0 }
+    }
+
+    /// Insert or update an entry, evicting LRU entries until the total weight fits.
+    ///
+    /// - `Ok(Some(old))` if the key existed; the value is replaced and promoted to MRU.
+    /// - `Ok(None)` if the key was inserted.
+    /// - `Err((key, value))` if the entry alone weighs more than the budget; the cache
+    ///   is left untouched, including any existing entry for the key.
+    /// - `Err((key, value))` if the underlying cache rejects a new key, e.g. because it is
+    ///   full and every entry is pinned; entries already evicted for its weight stay gone.
+    #[inline]
+    pub fn push(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        let weight = self.weigher.weight(&key, &value);
+        if weight > self.budget {
+            return Err((key, value));
+        }
+
+        if let Some(index) = self.cache.find_key_index(&key) {
+            let old = self.cache.update_at(index, Weighted { value, weight });
+            self.total = self.total - old.weight + weight;
+            // The updated entry is now MRU and fits on its own, so it is never evicted here
+            self.evict_to(self.budget);
+            return Ok(Some(old.value));
+        }
+
+        self.evict_to(self.budget - weight);
+        // Still bounded by the entry count too
+        match self.cache.try_push_evict(key, Weighted { value, weight }) {
+            Ok(evicted) => {
+                if let Some((_, evicted)) = evicted {
+                    self.total -= evicted.weight;
+                }
+                self.total += weight;
+                Ok(None)
+            }
+            Err((key, rejected)) => Err((key, rejected.value)),
+        }
+    }
+
+    /// Get a value by key, promoting it to MRU.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.get(key).map(|w| &w.value)
+    }
+
+    /// Get a mutable reference by key, promoting it to MRU. The entry keeps the weight
+    /// it was pushed with.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.get_mut(key).map(|w| &mut w.value)
+    }
+
+    /// Peek at a value by key without promoting it.
+    #[inline]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.peek(key).map(|w| &w.value)
+    }
+
+    /// Returns `true` if the key is present. Does not promote.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.contains_key(key)
+    }
+
+    /// Remove an entry and return it.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, w) = self.cache.remove(key)?;
+        self.total -= w.weight;
+        Some((key, w.value))
+    }
+
+    /// Remove and return the least recently used entry.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let (key, w) = self.cache.pop()?;
+        self.total -= w.weight;
+        Some((key, w.value))
+    }
+
+    /// Remove all entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.total = 0;
+    }
+
+    /// Change the weight budget, evicting LRU entries until the total fits.
+    #[inline]
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict_to(budget);
+    }
+
+    /// Returns the weight budget.
+    #[inline]
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Returns the summed weight of all entries.
+    #[inline]
+    pub fn total_weight(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if the cache holds no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the maximum number of entries, regardless of weight.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+
+    /// Returns `true` if the entries have spilled to the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        self.cache.is_spilled()
+    }
+
+    /// Returns a snapshot of the operation counters (`stats` feature).
+    ///
+    /// Entries evicted to fit the budget count as evictions.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> crate::CacheStats {
+        self.cache.stats()
+    }
+
+    /// Zero all operation counters (`stats` feature).
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.cache.reset_stats();
+    }
+
+    /// Evict LRU entries until the total weight is at most `limit`.
+    #[inline(always)]
+    fn evict_to(&mut self, limit: usize) {
+        while self.total > limit {
+            let (_, w) = self.cache.pop().expect("total weight is zero when empty");
+            self.total -= w.weight;
+            self.cache.counters.evict();
+        }
+    }
+}